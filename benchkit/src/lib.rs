@@ -0,0 +1,35 @@
+//! Deterministic input generation and throughput formatting shared by benches and generated tests.
+
+use std::time::Instant;
+
+/// Xorshift64 generator: fast, seedable and good enough for synthetic puzzle input.
+#[derive(Debug, Clone)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// A zero seed would only ever produce zeros, so it is replaced by a fixed non-zero one.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        XorShift(if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed })
+    }
+
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
+
+/// Time since `start` and the rate of `count` items per second, e.g. `12.34ms 1.62 Msteps/s`.
+#[must_use]
+pub fn rate(count: u32, unit: &str, start: Instant) -> String {
+    let elapsed = start.elapsed();
+    let per_second = f64::from(count) / elapsed.as_secs_f64();
+    let (scaled, prefix) = match per_second {
+        r if r >= 1e6 => (r / 1e6, "M"),
+        r if r >= 1e3 => (r / 1e3, "k"),
+        r => (r, ""),
+    };
+    format!("{elapsed:>10.2?} {scaled:>8.2} {prefix}{unit}/s")
+}
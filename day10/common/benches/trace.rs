@@ -0,0 +1,63 @@
+use benchkit::rate;
+use day10common::{draw_loop, Grid, Tile, TileType};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+
+const SIZE: i64 = 2000;
+const TILES: u32 = 2000 * 2000;
+
+/// A serpentine loop through every tile of a `SIZE` square, returning to the start up column 0.
+fn serpentine() -> Vec<(i64, i64)> {
+    let mut path = Vec::new();
+    for y in 0..SIZE {
+        if y % 2 == 0 {
+            path.extend((1..SIZE).map(|x| (x, y)));
+        } else {
+            path.extend((1..SIZE).rev().map(|x| (x, y)));
+        }
+    }
+    path.extend((0..SIZE).rev().map(|y| (0, y)));
+    path
+}
+
+fn render(path: &[(i64, i64)]) -> Vec<String> {
+    let size = usize::try_from(SIZE).unwrap_or_default();
+    let mut rows = vec![vec!['.'; size]; size];
+    draw_loop(&mut rows, path).unwrap_or_else(|err| panic!("{err}"));
+    rows.into_iter().map(String::from_iter).collect()
+}
+
+fn main() {
+    let lines = render(&serpentine());
+
+    let start = Instant::now();
+    let grid = Grid::parse(lines).unwrap_or_else(|err| panic!("{err}"));
+    println!("{:>16}: {}", "parse", rate(TILES, "tiles", start));
+
+    let start = Instant::now();
+    let circuit = black_box(grid.trace().unwrap_or_else(|err| panic!("{err}")));
+    let length = u32::try_from(circuit.len()).unwrap_or_default();
+    println!("{:>16}: {}", "dense trace", rate(length, "tiles", start));
+
+    let start_type = grid.start_type().unwrap_or_else(|err| panic!("{err}"));
+    let tiles = (0..SIZE)
+        .flat_map(|y| (0..SIZE).map(move |x| (x, y)))
+        .filter_map(|(x, y)| match grid.get(x, y) {
+            Tile::Pipe(tile_type) => Some(((x, y), tile_type)),
+            Tile::Start => Some(((x, y), start_type)),
+            Tile::Ground => None,
+        })
+        .collect::<HashMap<(i64, i64), TileType>>();
+    let start = Instant::now();
+    let mut length = 1;
+    let mut previous = grid.start;
+    let mut position = start_type.neighbours(grid.start.0, grid.start.1)[0];
+    while position != grid.start {
+        let [first, second] = tiles[&position].neighbours(position.0, position.1);
+        (previous, position) = (position, if first == previous { second } else { first });
+        length += 1;
+    }
+    black_box(length);
+    println!("{:>16}: {}", "hashed trace", rate(length, "tiles", start));
+}
@@ -0,0 +1,551 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TileType {
+    NE,
+    NS,
+    NW,
+    EW,
+    ES,
+    SW,
+}
+
+impl TileType {
+    #[must_use]
+    pub fn all_types() -> [TileType; 6] {
+        [
+            TileType::NE,
+            TileType::NS,
+            TileType::NW,
+            TileType::EW,
+            TileType::ES,
+            TileType::SW,
+        ]
+    }
+
+    #[must_use]
+    pub fn zoom(self) -> [[bool; 3]; 3] {
+        let o = true;
+        let x = false;
+
+        match self {
+            TileType::NE => [
+                [x, o, x], //
+                [x, o, o], //
+                [x, x, x], //
+            ], //
+            TileType::NS => [
+                [x, o, x], //
+                [x, o, x], //
+                [x, o, x], //
+            ], //
+            TileType::NW => [
+                [x, o, x], //
+                [o, o, x], //
+                [x, x, x], //
+            ], //
+            TileType::EW => [
+                [x, x, x], //
+                [o, o, o], //
+                [x, x, x], //
+            ], //
+            TileType::ES => [
+                [x, x, x], //
+                [x, o, o], //
+                [x, o, x], //
+            ], //
+            TileType::SW => [
+                [x, x, x], //
+                [o, o, x], //
+                [x, o, x], //
+            ], //
+        }
+    }
+
+    #[must_use]
+    pub fn neighbours(self, x: i64, y: i64) -> [(i64, i64); 2] {
+        match self {
+            TileType::NE => [(x, y - 1), (x + 1, y)],
+            TileType::NS => [(x, y - 1), (x, y + 1)],
+            TileType::NW => [(x, y - 1), (x - 1, y)],
+            TileType::EW => [(x + 1, y), (x - 1, y)],
+            TileType::ES => [(x + 1, y), (x, y + 1)],
+            TileType::SW => [(x, y + 1), (x - 1, y)],
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            TileType::NE => 'L',
+            TileType::NS => '|',
+            TileType::NW => 'J',
+            TileType::EW => '-',
+            TileType::ES => 'F',
+            TileType::SW => '7',
+        }
+    }
+
+    fn connects(self, (x, y): (i64, i64), to: (i64, i64)) -> bool {
+        self.neighbours(x, y).contains(&to)
+    }
+}
+
+impl Display for TileType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tile {
+    Pipe(TileType),
+    Ground,
+    Start,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(Tile::Pipe(match value {
+            '|' => TileType::NS,
+            '-' => TileType::EW,
+            'L' => TileType::NE,
+            'J' => TileType::NW,
+            '7' => TileType::SW,
+            'F' => TileType::ES,
+            '.' => return Ok(Tile::Ground),
+            'S' => return Ok(Tile::Start),
+            c => Err(format!("invalid character: {c}"))?,
+        }))
+    }
+}
+
+/// Where following the pipes from the start tile breaks off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadEnd {
+    pub from: (i64, i64),
+    pub to: (i64, i64),
+    /// The tile `to` holds, `None` outside the grid.
+    pub found: Option<Tile>,
+}
+
+impl Display for DeadEnd {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ((from_x, from_y), (x, y)) = (self.from, self.to);
+        write!(f, "pipe at ({from_x} {from_y}) is a dead end: ")?;
+        match self.found {
+            None => write!(f, "it leads outside the grid at ({x} {y})"),
+            Some(Tile::Ground) => write!(f, "it leads to ground at ({x} {y})"),
+            Some(Tile::Pipe(tile_type)) => {
+                write!(f, "it leads to `{tile_type}` at ({x} {y}), which does not connect back")
+            }
+            Some(Tile::Start) => write!(
+                f,
+                "it leads back into the start tile from a side the start does not open to"
+            ),
+        }
+    }
+}
+
+impl Error for DeadEnd {}
+
+/// Loop tiles in order, starting from the start tile.
+pub type Circuit = Vec<(i64, i64)>;
+
+/// Draws the closed loop through `circuit` onto `rows`, marking its first tile `S`, as the inverse
+/// of [`Grid::trace`].
+///
+/// # Errors
+///
+/// Fails when no pipe joins a tile to its neighbours in the loop, or a tile lies outside `rows`.
+pub fn draw_loop(rows: &mut [Vec<char>], circuit: &[(i64, i64)]) -> Result<(), String> {
+    for (i, &(x, y)) in circuit.iter().enumerate() {
+        let previous = circuit[(i + circuit.len() - 1) % circuit.len()];
+        let next = circuit[(i + 1) % circuit.len()];
+        let symbol = if i == 0 {
+            'S'
+        } else {
+            TileType::all_types()
+                .into_iter()
+                .find(|tile_type| tile_type.connects((x, y), previous) && tile_type.connects((x, y), next))
+                .ok_or_else(|| format!("no pipe at ({x}, {y}) joins {previous:?} and {next:?}"))?
+                .symbol()
+        };
+        let tile = usize::try_from(y)
+            .ok()
+            .and_then(|y| rows.get_mut(y))
+            .zip(usize::try_from(x).ok())
+            .and_then(|(row, x)| row.get_mut(x))
+            .ok_or_else(|| format!("({x}, {y}) is outside the grid"))?;
+        *tile = symbol;
+    }
+    Ok(())
+}
+
+/// A pipe the start tile could be, with the loop it closes or where it breaks off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub tile_type: TileType,
+    pub circuit: Result<Circuit, DeadEnd>,
+}
+
+/// How [`Grid::infer_start`] arrived at its candidates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartInference {
+    pub position: (i64, i64),
+    /// Neighbours holding pipes that lead into the start tile.
+    pub connected: Vec<(i64, i64)>,
+    pub candidates: Vec<Candidate>,
+}
+
+impl Display for StartInference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (x, y) = self.position;
+        let connected = self
+            .connected
+            .iter()
+            .map(|(x, y)| format!("({x} {y})"))
+            .collect::<Vec<_>>();
+        match connected.len() {
+            0 => write!(f, "start tile at ({x} {y}): no neighbouring pipe connects to it")?,
+            _ => write!(f, "start tile at ({x} {y}): connected to {}", connected.join(", "))?,
+        }
+        if self.candidates.is_empty() {
+            write!(f, "; no pipe connects two of those neighbours")?;
+        }
+        for candidate in &self.candidates {
+            match &candidate.circuit {
+                Ok(circuit) => write!(
+                    f,
+                    "; as `{}`: closes a loop of {} tiles",
+                    candidate.tile_type,
+                    circuit.len()
+                )?,
+                Err(dead_end) => write!(f, "; as `{}`: {dead_end}", candidate.tile_type)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How [`Grid::enclosed`] counts the tiles inside the loop.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Solver {
+    /// Flood-fills the outside of the loop drawn at three times the scale.
+    Zoom,
+    /// Shoelace area of the loop, converted to a tile count with Pick's theorem.
+    Shoelace,
+    /// Counts the tiles of each row crossed by an odd number of loop pipes reaching north.
+    Scanline,
+}
+
+impl FromStr for Solver {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "zoom" => Solver::Zoom,
+            "shoelace" => Solver::Shoelace,
+            "scanline" => Solver::Scanline,
+            _ => Err(format!("invalid solver {s}, expected zoom, shoelace or scanline"))?,
+        })
+    }
+}
+
+trait PaintMapTrait {
+    fn paint(&mut self, x: i64, y: i64, tile_type: TileType);
+}
+
+type PaintMap = HashSet<(i64, i64)>;
+impl PaintMapTrait for PaintMap {
+    fn paint(&mut self, x: i64, y: i64, tile_type: TileType) {
+        for (delta_y, row) in (0..).zip(tile_type.zoom()) {
+            for (delta_x, occupied) in (0..).zip(row) {
+                let (x, y) = (x * 3 + delta_x, y * 3 + delta_y);
+                if occupied {
+                    self.insert((x, y));
+                }
+            }
+        }
+    }
+}
+
+/// Row-major tiles, shorter lines padded with ground.
+#[derive(Debug)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+    pub start: (i64, i64),
+}
+
+impl Grid {
+    /// # Errors
+    ///
+    /// Fails on invalid characters, and when there is not exactly one start tile.
+    pub fn parse(lines: impl IntoIterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let mut rows = Vec::new();
+        let mut start = None;
+        for (y, line) in lines.into_iter().enumerate() {
+            let row = line.chars().map(Tile::try_from).collect::<Result<Vec<_>, _>>()?;
+            for x in (0..row.len()).filter(|&x| row[x] == Tile::Start) {
+                let (x, y) = (i64::try_from(x)?, i64::try_from(y)?);
+                if let Some((start_x, start_y)) = start {
+                    Err(format!(
+                        "start already given at ({start_x} {start_y}), another start at ({x} {y})"
+                    ))?;
+                }
+                start = Some((x, y));
+            }
+            rows.push(row);
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let height = rows.len();
+        let mut tiles = Vec::with_capacity(width * height);
+        for row in rows {
+            let padding = width - row.len();
+            tiles.extend(row);
+            tiles.extend(std::iter::repeat_n(Tile::Ground, padding));
+        }
+        Ok(Grid {
+            width,
+            height,
+            tiles,
+            start: start.ok_or("no starting point was found")?,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The tile at `(x, y)`, ground outside the grid.
+    #[must_use]
+    pub fn get(&self, x: i64, y: i64) -> Tile {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) if x < self.width && y < self.height => self.tiles[y * self.width + x],
+            _ => Tile::Ground,
+        }
+    }
+
+    fn pipe(&self, (x, y): (i64, i64)) -> Option<TileType> {
+        match self.get(x, y) {
+            Tile::Pipe(tile_type) => Some(tile_type),
+            Tile::Ground | Tile::Start => None,
+        }
+    }
+
+    fn contains(&self, (x, y): (i64, i64)) -> bool {
+        usize::try_from(x).is_ok_and(|x| x < self.width) && usize::try_from(y).is_ok_and(|y| y < self.height)
+    }
+
+    /// Every pipe the start tile could be given the neighbours that connect back to it, each
+    /// followed around to see whether it closes a loop.
+    #[must_use]
+    pub fn infer_start(&self) -> StartInference {
+        let (x, y) = self.start;
+        let connected = [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+            .into_iter()
+            .filter(|&position| {
+                self.pipe(position)
+                    .is_some_and(|tile_type| tile_type.connects(position, self.start))
+            })
+            .collect::<Vec<_>>();
+        let candidates = TileType::all_types()
+            .into_iter()
+            .filter(|tile_type| {
+                tile_type
+                    .neighbours(x, y)
+                    .iter()
+                    .all(|position| connected.contains(position))
+            })
+            .map(|tile_type| Candidate {
+                tile_type,
+                circuit: self.follow(tile_type),
+            })
+            .collect();
+        StartInference {
+            position: self.start,
+            connected,
+            candidates,
+        }
+    }
+
+    /// Follows the pipes from the start tile, taken to be `start_type`, until they return to it.
+    fn follow(&self, start_type: TileType) -> Result<Circuit, DeadEnd> {
+        let mut path = vec![self.start];
+        let mut previous = self.start;
+        let mut position = start_type.neighbours(self.start.0, self.start.1)[0];
+        loop {
+            let tile_type = self
+                .pipe_or_start(position, start_type)
+                .filter(|tile_type| tile_type.connects(position, previous))
+                .ok_or_else(|| DeadEnd {
+                    from: previous,
+                    to: position,
+                    found: self.contains(position).then(|| self.get(position.0, position.1)),
+                })?;
+            if position == self.start {
+                return Ok(path);
+            }
+            path.push(position);
+            let [first, second] = tile_type.neighbours(position.0, position.1);
+            (previous, position) = (position, if first == previous { second } else { first });
+        }
+    }
+
+    /// The start tile resolved to the one candidate closing a loop, with that loop.
+    fn resolve_start(&self) -> Result<(TileType, Circuit), Box<dyn Error>> {
+        let mut inference = self.infer_start();
+        let mut closing = (0..inference.candidates.len()).filter(|&i| inference.candidates[i].circuit.is_ok());
+        match (closing.next(), closing.next()) {
+            (Some(i), None) => {
+                let Candidate { tile_type, circuit } = inference.candidates.swap_remove(i);
+                Ok((tile_type, circuit?))
+            }
+            (None, _) => Err(format!("no pipe under the start tile closes a loop: {inference}"))?,
+            (Some(_), Some(_)) => Err(format!("several pipes under the start tile close a loop: {inference}"))?,
+        }
+    }
+
+    /// The pipe under the start tile, the only candidate from [`Grid::infer_start`] that closes a
+    /// loop.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless exactly one candidate closes a loop, listing every candidate and why it fails.
+    pub fn start_type(&self) -> Result<TileType, Box<dyn Error>> {
+        Ok(self.resolve_start()?.0)
+    }
+
+    /// The pipe at `position`, with the start tile resolved to `start_type`.
+    fn pipe_or_start(&self, position: (i64, i64), start_type: TileType) -> Option<TileType> {
+        if position == self.start {
+            Some(start_type)
+        } else {
+            self.pipe(position)
+        }
+    }
+
+    /// Follows the loop through the start tile once, returning its tiles in order from the start.
+    ///
+    /// # Errors
+    ///
+    /// As [`Grid::start_type`].
+    pub fn trace(&self) -> Result<Circuit, Box<dyn Error>> {
+        Ok(self.resolve_start()?.1)
+    }
+
+    /// Number of tiles enclosed by the loop through the start tile.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the loop cannot be traced.
+    pub fn enclosed(&self, solver: Solver) -> Result<usize, Box<dyn Error>> {
+        let (start_type, circuit) = self.resolve_start()?;
+        let pipes = circuit
+            .iter()
+            .map(|&position| (position, self.pipe_or_start(position, start_type).unwrap_or(start_type)));
+        Ok(match solver {
+            Solver::Zoom => zoom(pipes),
+            Solver::Shoelace => shoelace(&circuit),
+            Solver::Scanline => self.scanline(pipes),
+        })
+    }
+
+    fn scanline(&self, pipes: impl Iterator<Item = ((i64, i64), TileType)>) -> usize {
+        let mut on_loop = vec![None; self.tiles.len()];
+        for ((x, y), tile_type) in pipes {
+            let (x, y) = (
+                usize::try_from(x).unwrap_or_default(),
+                usize::try_from(y).unwrap_or_default(),
+            );
+            on_loop[y * self.width + x] = Some(tile_type);
+        }
+
+        let mut sum = 0;
+        for row in on_loop.chunks(self.width.max(1)) {
+            let mut inside = false;
+            for tile in row {
+                match tile {
+                    Some(TileType::NE | TileType::NS | TileType::NW) => inside = !inside,
+                    None if inside => sum += 1,
+                    Some(_) | None => (),
+                }
+            }
+        }
+        sum
+    }
+}
+
+fn zoom(pipes: impl Iterator<Item = ((i64, i64), TileType)>) -> usize {
+    let mut painted_map = HashSet::new();
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (i64::MAX, i64::MAX, i64::MIN, i64::MIN);
+    for ((x, y), tile_type) in pipes {
+        painted_map.paint(x, y, tile_type);
+        (min_x, min_y, max_x, max_y) = (
+            (x * 3).min(min_x),
+            (y * 3).min(min_y),
+            (x * 3 + 2).max(max_x),
+            (y * 3 + 2).max(max_y),
+        );
+    }
+
+    let mut queue = Vec::from([(min_x, min_y)]);
+    painted_map.insert((min_x, min_y));
+
+    while let Some((x, y)) = queue.pop() {
+        for (delta_x, delta_y) in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)] {
+            let x = x + delta_x;
+            let y = y + delta_y;
+            if !(min_x..=max_x).contains(&x) || !(min_y..=max_y).contains(&y) {
+                continue;
+            }
+            if painted_map.insert((x, y)) {
+                queue.push((x, y));
+            }
+        }
+    }
+
+    (min_y..max_y)
+        .step_by(3)
+        .map(|y| {
+            (min_x..max_x)
+                .step_by(3)
+                .filter(|x| {
+                    (0..3).all(|delta_y| {
+                        (0..3).all(|delta_x| {
+                            let x = x + delta_x;
+                            let y = y + delta_y;
+                            !painted_map.contains(&(x, y))
+                        })
+                    })
+                })
+                .count()
+        })
+        .sum::<usize>()
+}
+
+/// Pick's theorem gives `interior = area - boundary / 2 + 1`, with the loop tiles as the boundary
+/// points and twice the area from the shoelace formula.
+fn shoelace(circuit: &[(i64, i64)]) -> usize {
+    let twice_area = circuit
+        .iter()
+        .zip(circuit.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i64>()
+        .unsigned_abs();
+    let boundary = u64::try_from(circuit.len()).unwrap_or_default();
+    usize::try_from((twice_area + 2 - boundary) / 2).unwrap_or_default()
+}
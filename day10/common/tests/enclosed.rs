@@ -0,0 +1,151 @@
+use benchkit::XorShift;
+use day10common::{draw_loop, Grid, Solver};
+use std::collections::HashSet;
+
+const EXAMPLES: [(&str, usize); 4] = [
+    (
+        "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+        4,
+    ),
+    (
+        "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        4,
+    ),
+    (
+        ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+        8,
+    ),
+    (
+        "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        10,
+    ),
+];
+
+fn index(n: i64) -> usize {
+    usize::try_from(n).unwrap()
+}
+
+fn solve(grid: &Grid) -> [usize; 3] {
+    [Solver::Zoom, Solver::Shoelace, Solver::Scanline]
+        .map(|solver| grid.enclosed(solver).unwrap_or_else(|err| panic!("{solver:?}: {err}")))
+}
+
+/// A loop along the top and bottom of random column spans, returned as `(x, y)` tiles in order,
+/// or `None` when the two sides touch.
+fn column_loop(rng: &mut XorShift, width: i64, height: i64) -> Option<Vec<(i64, i64)>> {
+    let spans = (0..width)
+        .map(|_| {
+            let top = rng.next_below(height.unsigned_abs());
+            let bottom = top + 2 + rng.next_below((height + 2).unsigned_abs() - top);
+            (top.cast_signed() + 1, bottom.cast_signed() + 1)
+        })
+        .collect::<Vec<_>>();
+
+    let mut corners = vec![(1, spans[0].0)];
+    for x in 1..width {
+        corners.extend([(x + 1, spans[index(x) - 1].0), (x + 1, spans[index(x)].0)]);
+    }
+    for x in (1..width).rev() {
+        corners.extend([(x + 1, spans[index(x)].1), (x, spans[index(x)].1)]);
+    }
+    corners.push((1, spans[0].1));
+
+    let mut path = Vec::new();
+    for (i, &(x, y)) in corners.iter().enumerate() {
+        let (next_x, next_y) = corners[(i + 1) % corners.len()];
+        let (mut x, mut y) = (x, y);
+        while (x, y) != (next_x, next_y) {
+            path.push((x, y));
+            x += (next_x - x).signum();
+            y += (next_y - y).signum();
+        }
+    }
+    let unique = path.iter().collect::<HashSet<_>>();
+    (unique.len() == path.len() && path.len() >= 4).then_some(path)
+}
+
+fn render(rng: &mut XorShift, path: &[(i64, i64)], width: i64, height: i64) -> Vec<String> {
+    let junk = ['|', '-', 'L', 'J', '7', 'F', '.'];
+    let mut rows = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| junk[usize::try_from(rng.next_below(7)).unwrap()])
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let (start_x, start_y) = path[0];
+    for (x, y) in [
+        (start_x, start_y - 1),
+        (start_x + 1, start_y),
+        (start_x, start_y + 1),
+        (start_x - 1, start_y),
+    ] {
+        if let Some(tile) = rows.get_mut(index(y)).and_then(|row| row.get_mut(index(x))) {
+            *tile = '.';
+        }
+    }
+
+    draw_loop(&mut rows, path).unwrap();
+    rows.into_iter().map(String::from_iter).collect()
+}
+
+#[test]
+fn examples() {
+    for (input, expected) in EXAMPLES {
+        let grid = Grid::parse(input.lines().map(str::to_string)).unwrap();
+        assert_eq!(solve(&grid), [expected; 3], "{input}");
+    }
+}
+
+#[test]
+fn generated_loops_agree() {
+    let mut rng = XorShift::new(0x2023_1210);
+    let mut checked = 0;
+    while checked < 300 {
+        let width = 2 + rng.next_below(12).cast_signed();
+        let height = 2 + rng.next_below(12).cast_signed();
+        let Some(path) = column_loop(&mut rng, width, height) else {
+            continue;
+        };
+        let lines = render(&mut rng, &path, width + 3, height + 5);
+        let grid = Grid::parse(lines.clone()).unwrap();
+        let [zoom, shoelace, scanline] = solve(&grid);
+        assert_eq!(zoom, shoelace, "{}", lines.join("\n"));
+        assert_eq!(zoom, scanline, "{}", lines.join("\n"));
+        checked += 1;
+    }
+}
@@ -0,0 +1,42 @@
+use day10common::{DeadEnd, Grid, Tile, TileType};
+
+fn grid(input: &str) -> Grid {
+    Grid::parse(input.lines().map(str::to_string)).unwrap()
+}
+
+#[test]
+fn picks_the_candidate_closing_a_loop() {
+    let grid = grid(".|...\n-S-7.\n.|.|.\n.L-J.\n.....");
+    let inference = grid.infer_start();
+    assert_eq!(inference.connected, [(1, 0), (2, 1), (1, 2), (0, 1)]);
+    assert_eq!(inference.candidates.len(), 6);
+    assert_eq!(grid.start_type().unwrap(), TileType::ES);
+    assert_eq!(grid.trace().unwrap().len(), 8);
+}
+
+#[test]
+fn reports_dead_ends() {
+    let grid = grid(".....\n.S-7.\n.|.|.\n.L-..\n.....");
+    let inference = grid.infer_start();
+    assert_eq!(inference.candidates.len(), 1);
+    assert_eq!(
+        inference.candidates[0].circuit,
+        Err(DeadEnd {
+            from: (3, 2),
+            to: (3, 3),
+            found: Some(Tile::Ground),
+        })
+    );
+    let error = grid.trace().unwrap_err().to_string();
+    assert!(error.contains("pipe at (3 2) is a dead end"), "{error}");
+}
+
+#[test]
+fn rejects_several_closing_candidates() {
+    let grid = grid(".F-7.\nFS-J.\n|L7..\nL-J..");
+    let error = grid.start_type().unwrap_err().to_string();
+    assert!(
+        error.starts_with("several pipes under the start tile close a loop"),
+        "{error}"
+    );
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::Range;
+use tokenizer::embedded_numbers;
+
+#[derive(Debug)]
+pub struct Symbol {
+    pub c: char,
+    /// Character column, not byte offset.
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug)]
+pub struct Number {
+    pub n: i64,
+    pub y: usize,
+    /// Byte offsets in the line, as reported in parse errors.
+    pub span: Range<usize>,
+    /// Character columns, which adjacency is measured in.
+    pub columns: Range<usize>,
+}
+
+impl Number {
+    fn neighbours(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let left = self.columns.start.checked_sub(1);
+        let columns = left.unwrap_or(self.columns.start)..=self.columns.end;
+        let rows = self.y.checked_sub(1).into_iter().chain([self.y + 1]);
+
+        rows.flat_map(move |y| columns.clone().map(move |x| (x, y)))
+            .chain(left.map(|x| (x, self.y)))
+            .chain([(self.columns.end, self.y)])
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ParseOptions {
+    /// Read a `-` directly in front of digits as a sign rather than a symbol.
+    pub signed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Schematic {
+    symbols: Vec<Symbol>,
+    numbers: Vec<Number>,
+    numbers_by_symbol: Vec<Vec<usize>>,
+    symbols_by_number: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    /// # Errors
+    ///
+    /// Fails if a number does not fit in an `i64`.
+    pub fn parse<I, S>(lines: I) -> Result<Self, Box<dyn Error>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::parse_with(lines, ParseOptions::default())
+    }
+
+    /// # Errors
+    ///
+    /// Fails if a number does not fit in an `i64`.
+    pub fn parse_with<I, S>(lines: I, options: ParseOptions) -> Result<Self, Box<dyn Error>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut schematic = Schematic::default();
+
+        for (y, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            let first = schematic.numbers.len();
+            let (mut byte, mut column) = (0, 0);
+            for token in embedded_numbers(line, options.signed) {
+                let (n, span) = token.map_err(|e| format!("line {y}: {e}"))?;
+                // numbers are all ASCII, so only the text before them can hold wider characters
+                column += line[byte..span.start].chars().count();
+                byte = span.end;
+                let columns = column..column + span.len();
+                column = columns.end;
+                schematic.numbers.push(Number { n, y, span, columns });
+            }
+
+            let mut spans = schematic.numbers[first..].iter().map(|number| &number.span).peekable();
+            for (x, (offset, c)) in line.char_indices().enumerate() {
+                while spans.next_if(|span| span.end <= offset).is_some() {}
+                if c != '.' && !spans.peek().is_some_and(|span| span.contains(&offset)) {
+                    schematic.symbols.push(Symbol { c, x, y });
+                }
+            }
+        }
+
+        schematic.index();
+        Ok(schematic)
+    }
+
+    fn index(&mut self) {
+        let positions = self
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(i, symbol)| ((symbol.x, symbol.y), i))
+            .collect::<HashMap<_, _>>();
+
+        self.numbers_by_symbol = vec![Vec::new(); self.symbols.len()];
+        self.symbols_by_number = self
+            .numbers
+            .iter()
+            .enumerate()
+            .map(|(number_index, number)| {
+                number
+                    .neighbours()
+                    .filter_map(|position| positions.get(&position).copied())
+                    .inspect(|&symbol_index| self.numbers_by_symbol[symbol_index].push(number_index))
+                    .collect()
+            })
+            .collect();
+    }
+
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    #[must_use]
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn adjacent_numbers(&self, symbol: usize) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers_by_symbol[symbol].iter().map(|&i| &self.numbers[i])
+    }
+
+    pub fn adjacent_symbols(&self, number: usize) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols_by_number[number].iter().map(|&i| &self.symbols[i])
+    }
+
+    /// Numbers touching at least one symbol, each reported once.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter_map(|(number, symbols)| (!symbols.is_empty()).then_some(number))
+    }
+
+    /// Numbers touching at least one `c` symbol, each reported once.
+    pub fn numbers_adjacent_to(&self, c: char) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter_map(move |(number, symbols)| symbols.iter().any(|&i| self.symbols[i].c == c).then_some(number))
+    }
+
+    pub fn isolated_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter_map(|(number, symbols)| symbols.is_empty().then_some(number))
+    }
+
+    pub fn symbols_with_adjacent(&self, count: usize) -> impl Iterator<Item = (&Symbol, Vec<&Number>)> + '_ {
+        self.symbols
+            .iter()
+            .zip(&self.numbers_by_symbol)
+            .filter(move |(_, numbers)| numbers.len() == count)
+            .map(|(symbol, numbers)| (symbol, numbers.iter().map(|&i| &self.numbers[i]).collect()))
+    }
+}
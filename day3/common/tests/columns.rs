@@ -0,0 +1,15 @@
+use day3common::Schematic;
+
+#[test]
+fn wide_symbols_take_one_column() {
+    let schematic = Schematic::parse(["€12", "..€", "ü.7"]).unwrap();
+    assert_eq!(schematic.isolated_numbers().count(), 0);
+    assert_eq!(schematic.numbers()[0].span, 3..5);
+    assert_eq!(schematic.numbers()[0].columns, 1..3);
+    assert_eq!(schematic.numbers()[1].columns, 2..3);
+    let euro = schematic.symbols().iter().filter(|symbol| symbol.c == '€');
+    assert_eq!(
+        euro.map(|symbol| (symbol.x, symbol.y)).collect::<Vec<_>>(),
+        [(0, 0), (2, 1)]
+    );
+}
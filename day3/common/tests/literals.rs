@@ -0,0 +1,41 @@
+use day3common::{ParseOptions, Schematic};
+
+fn numbers(schematic: &Schematic) -> Vec<(i64, std::ops::Range<usize>)> {
+    schematic
+        .numbers()
+        .iter()
+        .map(|number| (number.n, number.span.clone()))
+        .collect()
+}
+
+#[test]
+fn zero_and_leading_zeros_keep_their_width() {
+    let schematic = Schematic::parse(["0.007", "....*"]).unwrap();
+    assert_eq!(numbers(&schematic), [(0, 0..1), (7, 2..5)]);
+    assert_eq!(
+        schematic.isolated_numbers().map(|number| number.n).collect::<Vec<_>>(),
+        [0]
+    );
+    assert_eq!(schematic.part_numbers().map(|number| number.n).collect::<Vec<_>>(), [7]);
+}
+
+#[test]
+fn minus_is_a_sign_only_when_asked_and_not_after_a_digit() {
+    let signed = ParseOptions { signed: true };
+
+    let schematic = Schematic::parse_with(["12-34", "....."], signed).unwrap();
+    assert_eq!(numbers(&schematic), [(12, 0..2), (34, 3..5)]);
+    assert_eq!(
+        schematic.symbols().iter().map(|symbol| symbol.c).collect::<Vec<_>>(),
+        ['-']
+    );
+    assert_eq!(schematic.part_numbers().count(), 2);
+
+    let schematic = Schematic::parse_with([".-12.", "....."], signed).unwrap();
+    assert_eq!(numbers(&schematic), [(-12, 1..4)]);
+    assert!(schematic.symbols().is_empty());
+
+    let schematic = Schematic::parse([".-12.", "....."]).unwrap();
+    assert_eq!(numbers(&schematic), [(12, 2..4)]);
+    assert_eq!(schematic.part_numbers().count(), 1);
+}
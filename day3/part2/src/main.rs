@@ -1,77 +1,17 @@
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use day3common::Schematic;
+use std::error::Error;
 use std::io::{stdin, BufRead};
 
-struct Number {
-    n: u32,
-    x: usize,
-    y: usize,
-}
-
-impl Number {
-    fn neighbours(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        let x: i32 = self.x.try_into().unwrap();
-        let y: i32 = self.y.try_into().unwrap();
-        let len: i32 = (self.n.ilog10() as usize + 1).try_into().unwrap();
-
-        let top = ((x - 1)..=(x + len)).map(move |x| (x, y - 1));
-        let bottom = ((x - 1)..=(x + len)).map(move |x| (x, y + 1));
-        let mid = [(x - 1, y), (x + len, y)];
-
-        top.chain(mid)
-            .chain(bottom)
-            .filter(|&(x, y)| x >= 0 && y >= 0)
-            .map(|(x, y)| (x.try_into().unwrap(), y.try_into().unwrap()))
-    }
-}
-
-fn main() {
+fn main() -> Result<(), Box<dyn Error>> {
     let lines = stdin().lock().lines();
-    let mut symbols = HashMap::new();
-
-    let numbers: Vec<_> = lines
-        .map_while(Result::ok)
-        .enumerate()
-        .flat_map(|(y, line)| {
-            for (x, c) in line.as_bytes().iter().enumerate() {
-                if !c.is_ascii_digit() && *c != b'.' {
-                    symbols.insert((x, y), Vec::with_capacity(2));
-                }
-            }
-
-            let mut x = 0;
-            line.split_terminator(|c: char| !c.is_ascii_digit())
-                .filter_map(|l| {
-                    let result = match l.parse::<u32>() {
-                        Ok(number) => Some(Number { n: number, x, y }),
-                        Err(_) => None,
-                    };
-                    x += l.len() + 1;
-                    result
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect();
-
-    for number in numbers {
-        for (x, y) in number.neighbours() {
-            let pos = (x, y);
-            if let Entry::Occupied(mut symbol) = symbols.entry(pos) {
-                let numbers = symbol.get_mut();
-                if numbers.len() == 2 {
-                    symbol.remove();
-                } else {
-                    numbers.push(number.n);
-                }
-            }
-        }
-    }
+    let schematic = Schematic::parse(lines.map_while(Result::ok))?;
 
-    let sum: u32 = symbols.values().fold(0, |acc, numbers| {
-        acc + (numbers.len() == 2)
-            .then(|| numbers[0] * numbers[1])
-            .unwrap_or_default()
-    });
+    let sum = schematic
+        .symbols_with_adjacent(2)
+        .filter(|(symbol, _)| symbol.c == '*')
+        .map(|(_, numbers)| numbers.iter().map(|number| number.n).product::<i64>())
+        .sum::<i64>();
 
     println!("{sum}");
+    Ok(())
 }
@@ -0,0 +1,41 @@
+use benchkit::{rate, XorShift};
+use day4common::WinningNumbers;
+use std::hint::black_box;
+use std::time::Instant;
+
+const CARDS: u32 = 2_000_000;
+
+fn generate(bound: u64) -> Vec<(Vec<u64>, Vec<u64>)> {
+    let mut rng = XorShift::new(0x2023_1204);
+    (0..CARDS)
+        .map(|_| {
+            let winning = (0..10).map(|_| rng.next_below(bound)).collect();
+            let numbers = (0..25).map(|_| rng.next_below(bound)).collect();
+            (winning, numbers)
+        })
+        .collect()
+}
+
+fn bench(name: &str, cards: &[(Vec<u64>, Vec<u64>)], build: impl Fn(&[u64]) -> WinningNumbers) {
+    let start = Instant::now();
+    let matches = cards
+        .iter()
+        .map(|(winning, numbers)| {
+            let winning = build(winning);
+            numbers.iter().filter(|&&n| winning.contains(n)).count()
+        })
+        .sum::<usize>();
+    println!("{name:>24}: {} ({matches} matches)", rate(CARDS, "cards", start));
+    black_box(matches);
+}
+
+fn main() {
+    for bound in [100, 1000, 100_000] {
+        let cards = generate(bound);
+        println!("numbers < {bound}");
+        bench("auto", &cards, |winning| winning.iter().copied().collect());
+        bench("hashed", &cards, |winning| {
+            WinningNumbers::hashed(winning.iter().copied())
+        });
+    }
+}
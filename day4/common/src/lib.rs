@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::str::FromStr;
+use tokenizer::numbers;
+
+#[derive(Debug)]
+pub enum WinningNumbers {
+    Small(u128),
+    Bounded(Box<[u64; 16]>),
+    Hashed(HashSet<u64>),
+}
+
+impl WinningNumbers {
+    pub fn hashed(numbers: impl IntoIterator<Item = u64>) -> Self {
+        WinningNumbers::Hashed(numbers.into_iter().collect())
+    }
+
+    #[must_use]
+    pub fn contains(&self, n: u64) -> bool {
+        match self {
+            WinningNumbers::Small(bits) => n < 128 && bits & (1 << n) != 0,
+            WinningNumbers::Bounded(words) => n < 1024 && words[(n / 64) as usize] & (1 << (n % 64)) != 0,
+            WinningNumbers::Hashed(set) => set.contains(&n),
+        }
+    }
+}
+
+impl FromIterator<u64> for WinningNumbers {
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        let numbers = iter.into_iter().collect::<Vec<_>>();
+        match numbers.iter().max() {
+            None => WinningNumbers::Small(0),
+            Some(&max) if max < 128 => WinningNumbers::Small(numbers.iter().fold(0, |bits, n| bits | (1 << n))),
+            Some(&max) if max < 1024 => {
+                WinningNumbers::Bounded(Box::new(numbers.iter().fold([0; 16], |mut words, n| {
+                    words[(n / 64) as usize] |= 1 << (n % 64);
+                    words
+                })))
+            }
+            Some(_) => WinningNumbers::hashed(numbers),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: usize,
+    pub winning: WinningNumbers,
+    pub numbers: Vec<u64>,
+}
+
+impl FromStr for Card {
+    type Err = Box<dyn Error>;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let colon = line.find(':').ok_or_else(|| format!("Malformed line: {line}"))?;
+        let bar = colon
+            + line[colon..]
+                .find('|')
+                .ok_or_else(|| format!("Malformed line: {line}"))?;
+        let header = &line[..colon];
+
+        let id = header
+            .strip_prefix("Card")
+            .ok_or_else(|| format!("Malformed card header: {header}"))?
+            .trim()
+            .parse()?;
+        let winning = numbers::<u64>(&line[colon + 1..bar])
+            .offset(colon + 1)
+            .map(|token| token.map(|(n, _)| n))
+            .collect::<Result<WinningNumbers, _>>()
+            .map_err(|e| format!("Malformed line {line}: {e}"))?;
+        let numbers = numbers::<u64>(&line[bar + 1..])
+            .offset(bar + 1)
+            .map(|token| token.map(|(n, _)| n))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Malformed line {line}: {e}"))?;
+
+        Ok(Card { id, winning, numbers })
+    }
+}
+
+impl Card {
+    #[must_use]
+    pub fn matches(&self) -> usize {
+        self.numbers.iter().filter(|&&n| self.winning.contains(n)).count()
+    }
+}
+
+#[derive(Debug)]
+pub struct Simulation {
+    /// Final number of instances held of each card, by table position.
+    pub instances: Vec<usize>,
+    /// For each card, the `(source, copies)` pairs of cards that won copies of it.
+    pub won_from: Vec<Vec<(usize, usize)>>,
+    /// Cards whose matches point past the end of the table, with how many of their matches were dropped.
+    pub out_of_range: Vec<(usize, usize)>,
+}
+
+impl Simulation {
+    #[must_use]
+    pub fn run(cards: &[Card]) -> Self {
+        let mut instances = vec![1; cards.len()];
+        let mut won_from = vec![Vec::new(); cards.len()];
+        let mut out_of_range = Vec::new();
+
+        for (index, card) in cards.iter().enumerate() {
+            let matches = card.matches();
+            let won = (index + 1..=index + matches).take_while(|&target| target < cards.len());
+            let mut in_range = 0;
+            for target in won {
+                instances[target] += instances[index];
+                won_from[target].push((index, instances[index]));
+                in_range += 1;
+            }
+            if in_range < matches {
+                out_of_range.push((index, matches - in_range));
+            }
+        }
+
+        Simulation {
+            instances,
+            won_from,
+            out_of_range,
+        }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.instances.iter().sum()
+    }
+}
@@ -0,0 +1,44 @@
+use day4common::{Card, Simulation};
+
+fn cards(input: &str) -> Vec<Card> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+#[test]
+fn example_table() {
+    let cards = cards(
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    );
+    let simulation = Simulation::run(&cards);
+    assert_eq!(simulation.instances, [1, 2, 4, 8, 14, 1]);
+    assert_eq!(simulation.total(), 30);
+    assert_eq!(simulation.won_from[0], []);
+    assert_eq!(simulation.won_from[4], [(0, 1), (2, 4), (3, 8)]);
+    assert_eq!(simulation.out_of_range, []);
+}
+
+#[test]
+fn wins_past_the_end_are_dropped() {
+    let cards = cards(
+        "Card 1: 1 2 3 | 1 2 3
+Card 2: 4 | 4 5
+Card 3: 6 7 | 7 6",
+    );
+    let simulation = Simulation::run(&cards);
+    assert_eq!(simulation.instances, [1, 2, 4]);
+    assert_eq!(simulation.total(), 7);
+    assert_eq!(simulation.won_from, [vec![], vec![(0, 1)], vec![(0, 1), (1, 2)]]);
+    assert_eq!(simulation.out_of_range, [(0, 1), (2, 2)]);
+}
+
+#[test]
+fn empty_table() {
+    let simulation = Simulation::run(&[]);
+    assert_eq!(simulation.total(), 0);
+    assert!(simulation.out_of_range.is_empty());
+}
@@ -0,0 +1,461 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub source_start: u64,
+    pub destination_start: u64,
+    pub len: u64,
+    /// One-based line number of the entry, zero when built by hand.
+    pub line: usize,
+}
+
+impl CategoryMap {
+    fn source(&self) -> Range<u64> {
+        self.source_start..self.source_start.saturating_add(self.len)
+    }
+
+    fn destination(&self) -> Range<u64> {
+        self.destination_start..self.destination_start.saturating_add(self.len)
+    }
+
+    #[allow(clippy::unnecessary_lazy_evaluations)]
+    fn convert(&self, number: u64) -> Option<u64> {
+        self.source()
+            .contains(&number)
+            .then(|| self.destination_start + number - self.source_start)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Range<u64>,
+    pub destination: u64,
+}
+
+impl Piece {
+    fn destination_range(&self) -> Range<u64> {
+        self.destination..self.destination.saturating_add(self.source.end - self.source.start)
+    }
+}
+
+/// Piecewise translation covering `0..u64::MAX`, with pieces sorted by source and contiguous.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    pieces: Vec<Piece>,
+}
+
+impl Default for RangeMap {
+    fn default() -> Self {
+        RangeMap {
+            pieces: vec![Piece {
+                source: 0..u64::MAX,
+                destination: 0,
+            }],
+        }
+    }
+}
+
+impl RangeMap {
+    /// Builds the map of one almanac block; where entries overlap the first one listed wins, and
+    /// numbers outside every entry map to themselves.
+    #[must_use]
+    pub fn new(entries: &[CategoryMap]) -> Self {
+        let mut bounds = entries
+            .iter()
+            .flat_map(|entry| [entry.source_start, entry.source_start.saturating_add(entry.len)])
+            .chain([0, u64::MAX])
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        Self::from_pieces(bounds.windows(2).map(|bounds| {
+            Piece {
+                source: bounds[0]..bounds[1],
+                destination: entries
+                    .iter()
+                    .find_map(|entry| entry.convert(bounds[0]))
+                    .unwrap_or(bounds[0]),
+            }
+        }))
+    }
+
+    fn from_pieces(pieces: impl IntoIterator<Item = Piece>) -> Self {
+        let pieces = pieces.into_iter().fold(Vec::<Piece>::new(), |mut pieces, piece| {
+            match pieces.last_mut() {
+                Some(last)
+                    if last.destination_range().end == piece.destination && last.source.end == piece.source.start =>
+                {
+                    last.source.end = piece.source.end;
+                }
+                _ => pieces.push(piece),
+            }
+            pieces
+        });
+        RangeMap { pieces }
+    }
+
+    #[must_use]
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    #[must_use]
+    pub fn get(&self, number: u64) -> u64 {
+        self.overlapping(number..number.saturating_add(1))
+            .next()
+            .map_or(number, |(_, destination)| destination)
+    }
+
+    /// Splits `range` along the pieces it crosses, yielding each part with the image of its start.
+    /// An empty range crosses nothing.
+    fn overlapping(&self, range: Range<u64>) -> impl Iterator<Item = (Range<u64>, u64)> + '_ {
+        let first = self.pieces.partition_point(|piece| piece.source.end <= range.start);
+        self.pieces[first..]
+            .iter()
+            .take_while(move |piece| piece.source.start < range.end)
+            .map(move |piece| {
+                let start = piece.source.start.max(range.start);
+                let end = piece.source.end.min(range.end);
+                (start..end, piece.destination + (start - piece.source.start))
+            })
+            .filter(|(part, _)| !part.is_empty())
+    }
+
+    #[must_use]
+    pub fn apply(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.overlapping(range)
+            .map(|(source, destination)| destination..destination + (source.end - source.start))
+            .collect()
+    }
+
+    /// The map applying `self` and then `other`.
+    #[must_use]
+    pub fn compose(&self, other: &RangeMap) -> RangeMap {
+        Self::from_pieces(self.pieces.iter().flat_map(|piece| {
+            other
+                .overlapping(piece.destination_range())
+                .map(|(middle, destination)| {
+                    let start = piece.source.start + (middle.start - piece.destination);
+                    Piece {
+                        source: start..start + (middle.end - middle.start),
+                        destination,
+                    }
+                })
+        }))
+    }
+
+    /// # Errors
+    ///
+    /// Fails when two pieces share destinations, or when some destination is never reached.
+    pub fn inverse(&self) -> Result<RangeMap, Box<dyn Error>> {
+        let mut pieces = self.pieces.clone();
+        pieces.sort_unstable_by_key(|piece| piece.destination);
+
+        let mut expected = 0;
+        for piece in &pieces {
+            let destination = piece.destination_range();
+            if destination.start < expected {
+                return Err(format!("not injective: destinations {destination:?} mapped more than once").into());
+            }
+            if destination.start > expected {
+                return Err(format!(
+                    "not surjective: destinations {:?} never reached",
+                    expected..destination.start
+                )
+                .into());
+            }
+            expected = destination.end;
+        }
+        if expected != u64::MAX {
+            return Err(format!("not surjective: destinations {:?} never reached", expected..u64::MAX).into());
+        }
+
+        Ok(Self::from_pieces(pieces.into_iter().map(|piece| Piece {
+            source: piece.destination_range(),
+            destination: piece.source.start,
+        })))
+    }
+
+    /// Walks images upward and returns the lowest one whose input lies in one of `sources`,
+    /// together with that input. Pieces are visited by destination, so no inverse is needed and
+    /// several inputs may share an image.
+    #[must_use]
+    pub fn lowest_image_from(&self, sources: &[Range<u64>]) -> Option<(u64, u64)> {
+        let mut pieces = self.pieces.iter().collect::<Vec<_>>();
+        pieces.sort_unstable_by_key(|piece| piece.destination);
+
+        let mut best: Option<(u64, u64)> = None;
+        for piece in pieces {
+            if best.is_some_and(|(_, image)| image <= piece.destination) {
+                break;
+            }
+            let Some(input) = sources
+                .iter()
+                .filter_map(|source| overlap(&piece.source, source))
+                .map(|range| range.start)
+                .min()
+            else {
+                continue;
+            };
+            let image = piece.destination + (input - piece.source.start);
+            if best.is_none_or(|(_, lowest)| image < lowest) {
+                best = Some((input, image));
+            }
+        }
+        best
+    }
+
+    #[must_use]
+    pub fn min_over(&self, range: Range<u64>) -> Option<u64> {
+        self.apply(range).into_iter().map(|range| range.start).min()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Two entries claim the same sources; lookups silently use the first one.
+    OverlappingSources { lines: (usize, usize), range: Range<u64> },
+    /// Two entries send different sources to the same destinations.
+    OverlappingDestinations { lines: (usize, usize), range: Range<u64> },
+    /// Sources between two entries that no entry covers, so they map to themselves.
+    Gap { lines: (usize, usize), range: Range<u64> },
+}
+
+impl Issue {
+    /// Gaps are legal in an almanac, overlaps make the answer depend on entry order.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Issue::Gap { .. })
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::OverlappingSources { lines, range } => {
+                write!(
+                    f,
+                    "lines {} and {}: source ranges overlap on {range:?}",
+                    lines.0, lines.1
+                )
+            }
+            Issue::OverlappingDestinations { lines, range } => {
+                write!(
+                    f,
+                    "lines {} and {}: destination ranges overlap on {range:?}",
+                    lines.0, lines.1
+                )
+            }
+            Issue::Gap { lines, range } => {
+                write!(
+                    f,
+                    "lines {} and {}: sources {range:?} are not covered",
+                    lines.0, lines.1
+                )
+            }
+        }
+    }
+}
+
+fn overlap(a: &Range<u64>, b: &Range<u64>) -> Option<Range<u64>> {
+    let range = a.start.max(b.start)..a.end.min(b.end);
+    (!range.is_empty()).then_some(range)
+}
+
+#[derive(Debug)]
+pub struct NamedMap {
+    pub source: String,
+    pub destination: String,
+    /// One-based line number of the map header.
+    pub line: usize,
+    pub entries: Vec<CategoryMap>,
+}
+
+#[derive(Debug, Default)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<NamedMap>,
+}
+
+impl Almanac {
+    /// # Errors
+    ///
+    /// Fails on malformed seeds, headers or map entries, and when the maps form a cycle.
+    pub fn parse(lines: impl IntoIterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let mut lines = lines.into_iter().zip(1..);
+        let seeds = lines
+            .next()
+            .ok_or("missing first line")?
+            .0
+            .split_once(": ")
+            .ok_or("invalid first line")?
+            .1
+            .split(' ')
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        let mut maps = Vec::new();
+
+        if !lines.next().ok_or("expected next blank line")?.0.is_empty() {
+            return Err("blank second line expected".into());
+        }
+
+        while let Some((header, line)) = lines.next() {
+            let (source, destination) = header
+                .strip_suffix(" map:")
+                .and_then(|names| names.split_once("-to-"))
+                .ok_or_else(|| format!("line {line}: expected `<source>-to-<destination> map:`, got {header}"))?;
+            let mut map = NamedMap {
+                source: source.to_string(),
+                destination: destination.to_string(),
+                line,
+                entries: Vec::new(),
+            };
+            for (entry, line) in lines.by_ref().take_while(|(entry, _)| !entry.is_empty()) {
+                let mut parts = entry.splitn(3, ' ').map(str::parse::<u64>);
+                let destination_start = parts
+                    .next()
+                    .ok_or_else(|| format!("line {line}: expected destination in {entry}"))??;
+                let source_start = parts
+                    .next()
+                    .ok_or_else(|| format!("line {line}: expected source in {entry}"))??;
+                let len = parts
+                    .next()
+                    .ok_or_else(|| format!("line {line}: expected length in {entry}"))??;
+                map.entries.push(CategoryMap {
+                    source_start,
+                    destination_start,
+                    len,
+                    line,
+                });
+            }
+            maps.push(map);
+        }
+
+        let almanac = Almanac { seeds, maps };
+        almanac.check_acyclic()?;
+        Ok(almanac)
+    }
+
+    #[must_use]
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        for map in &self.maps {
+            for (i, first) in map.entries.iter().enumerate() {
+                for second in &map.entries[i + 1..] {
+                    let lines = (first.line, second.line);
+                    if let Some(range) = overlap(&first.source(), &second.source()) {
+                        issues.push(Issue::OverlappingSources { lines, range });
+                    }
+                    if let Some(range) = overlap(&first.destination(), &second.destination()) {
+                        issues.push(Issue::OverlappingDestinations { lines, range });
+                    }
+                }
+            }
+
+            let mut entries = map.entries.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|entry| entry.source_start);
+            let Some((first, rest)) = entries.split_first() else {
+                continue;
+            };
+            let (mut line, mut end) = (first.line, first.source().end);
+            for entry in rest {
+                if entry.source_start > end {
+                    issues.push(Issue::Gap {
+                        lines: (line, entry.line),
+                        range: end..entry.source_start,
+                    });
+                }
+                if entry.source().end > end {
+                    (line, end) = (entry.line, entry.source().end);
+                }
+            }
+        }
+        issues
+    }
+
+    fn outgoing<'a>(&'a self, category: &'a str) -> impl Iterator<Item = &'a NamedMap> + 'a {
+        self.maps.iter().filter(move |map| map.source == category)
+    }
+
+    fn check_acyclic(&self) -> Result<(), Box<dyn Error>> {
+        fn visit<'a>(
+            almanac: &'a Almanac,
+            category: &'a str,
+            visiting: &mut Vec<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Result<(), Box<dyn Error>> {
+            if done.contains(category) {
+                return Ok(());
+            }
+            if let Some(start) = visiting.iter().position(|&c| c == category) {
+                return Err(format!("maps form a cycle: {} -> {category}", visiting[start..].join(" -> ")).into());
+            }
+            visiting.push(category);
+            for map in almanac.outgoing(category) {
+                visit(almanac, &map.destination, visiting, done)?;
+            }
+            visiting.pop();
+            done.insert(category);
+            Ok(())
+        }
+
+        let mut done = HashSet::new();
+        for map in &self.maps {
+            visit(self, &map.source, &mut Vec::new(), &mut done)?;
+        }
+        Ok(())
+    }
+
+    /// Maps leading from `from` to `to`, following the fewest maps.
+    fn route<'a>(&'a self, from: &'a str, to: &str) -> Option<Vec<&'a NamedMap>> {
+        let mut previous = HashMap::<&str, &NamedMap>::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut route = Vec::new();
+                let mut category = to;
+                while let Some(&map) = previous.get(category) {
+                    route.push(map);
+                    category = &map.source;
+                }
+                route.reverse();
+                return Some(route);
+            }
+            for map in self.outgoing(category) {
+                if map.destination != from && !previous.contains_key(map.destination.as_str()) {
+                    previous.insert(&map.destination, map);
+                    queue.push_back(&map.destination);
+                }
+            }
+        }
+        None
+    }
+
+    /// Composes the maps between two categories, inverting them when `to` comes before `from`.
+    ///
+    /// # Errors
+    ///
+    /// Fails when neither category can be reached from the other, or when a backward route is not
+    /// invertible.
+    pub fn path(&self, from: &str, to: &str) -> Result<RangeMap, Box<dyn Error>> {
+        let compose = |route: Vec<&NamedMap>| {
+            route.into_iter().fold(RangeMap::default(), |composed, map| {
+                composed.compose(&RangeMap::new(&map.entries))
+            })
+        };
+
+        if let Some(route) = self.route(from, to) {
+            return Ok(compose(route));
+        }
+        if let Some(route) = self.route(to, from) {
+            return compose(route)
+                .inverse()
+                .map_err(|e| format!("cannot map {from} back to {to}: {e}").into());
+        }
+        Err(format!("no maps lead from {from} to {to}").into())
+    }
+}
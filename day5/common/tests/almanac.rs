@@ -0,0 +1,182 @@
+use day5common::{Almanac, Issue};
+use std::ops::Range;
+
+const MAPS: &str = "seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+fn parse(input: &str) -> Almanac {
+    Almanac::parse(input.lines().map(str::to_string)).unwrap()
+}
+
+fn almanac(seeds: &str) -> Almanac {
+    parse(&format!("seeds: {seeds}\n\n{MAPS}"))
+}
+
+#[test]
+fn zero_length_seed_range_holds_no_seeds() {
+    let almanac = almanac("83 0 55 13");
+    let conversions = almanac.path("seed", "location").unwrap();
+    assert_eq!(conversions.min_over(83..83), None);
+    assert!(conversions.apply(83..83).is_empty());
+
+    let min = [83..83, 55..68]
+        .into_iter()
+        .filter_map(|range| conversions.min_over(range))
+        .min();
+    assert_eq!(min, Some(56));
+}
+
+fn seed_ranges(almanac: &Almanac) -> Vec<Range<u64>> {
+    almanac.seeds.chunks(2).map(|pair| pair[0]..pair[0] + pair[1]).collect()
+}
+
+/// Forward interval answer, reverse answer and the brute-force answer over every seed.
+fn solve(almanac: &Almanac) -> [Option<u64>; 3] {
+    let ranges = seed_ranges(almanac);
+    let conversions = almanac.path("seed", "location").unwrap();
+    let brute_force = ranges
+        .iter()
+        .flat_map(Clone::clone)
+        .map(|seed| conversions.get(seed))
+        .min();
+    let forward = ranges
+        .iter()
+        .filter_map(|range| conversions.min_over(range.clone()))
+        .min();
+    let reverse = conversions.lowest_image_from(&ranges);
+    if let Some((seed, location)) = reverse {
+        assert_eq!(conversions.get(seed), location);
+    }
+    [forward, reverse.map(|(_, location)| location), brute_force]
+}
+
+#[test]
+fn reverse_search_agrees_with_forward() {
+    for (seeds, expected) in [("79 14 55 13", 46), ("83 0 55 13", 56)] {
+        assert_eq!(solve(&almanac(seeds)), [Some(expected); 3], "{seeds}");
+    }
+    for seeds in ["0 100", "90 10", "14 1 50 48", "97 3 0 1"] {
+        let [forward, reverse, brute_force] = solve(&almanac(seeds));
+        assert_eq!(forward, brute_force, "{seeds}");
+        assert_eq!(reverse, brute_force, "{seeds}");
+    }
+}
+
+#[test]
+fn reverse_search_needs_no_bijection() {
+    assert_eq!(
+        solve(&parse(
+            "seeds: 10 3
+
+seed-to-location map:
+0 10 5"
+        )),
+        [Some(0); 3]
+    );
+    for maps in [
+        "seed-to-location map:\n2 0 5\n0 20 5",
+        "seed-to-location map:\n3 0 5\n0 5 5",
+        "seed-to-location map:\n40 0 30\n0 21 1",
+    ] {
+        let [forward, reverse, brute_force] = solve(&parse(&format!("seeds: 3 1 21 2 10 3\n\n{maps}")));
+        assert_eq!(forward, brute_force, "{maps}");
+        assert_eq!(reverse, brute_force, "{maps}");
+    }
+}
+
+/// Issues of a single `a-to-b` map whose entries start on line 4.
+fn issues(entries: &str) -> Vec<Issue> {
+    parse(&format!("seeds: 1 1\n\na-to-b map:\n{entries}")).validate()
+}
+
+#[test]
+fn example_has_no_errors() {
+    assert!(!almanac("79 14").validate().iter().any(Issue::is_error));
+}
+
+#[test]
+fn reports_overlapping_sources() {
+    assert_eq!(
+        issues("0 10 5\n100 12 5"),
+        [Issue::OverlappingSources {
+            lines: (4, 5),
+            range: 12..15
+        }]
+    );
+}
+
+#[test]
+fn reports_overlapping_destinations() {
+    assert_eq!(
+        issues("0 10 5\n3 15 5"),
+        [Issue::OverlappingDestinations {
+            lines: (4, 5),
+            range: 3..5
+        }]
+    );
+}
+
+#[test]
+fn reports_gaps_between_sorted_sources() {
+    let issues = issues("0 30 5\n10 10 5\n50 20 3");
+    assert_eq!(
+        issues,
+        [
+            Issue::Gap {
+                lines: (5, 6),
+                range: 15..20
+            },
+            Issue::Gap {
+                lines: (6, 4),
+                range: 23..30
+            },
+        ]
+    );
+    assert!(!issues.iter().any(Issue::is_error));
+}
+
+#[test]
+fn gaps_start_after_the_furthest_covering_entry() {
+    assert_eq!(
+        issues("0 0 20\n50 5 5\n70 25 5"),
+        [
+            Issue::OverlappingSources {
+                lines: (4, 5),
+                range: 5..10
+            },
+            Issue::Gap {
+                lines: (4, 6),
+                range: 20..25
+            },
+        ]
+    );
+}
@@ -0,0 +1,49 @@
+use num_bigint::BigUint;
+use std::ops::RangeInclusive;
+
+#[derive(Debug)]
+pub struct Race {
+    pub time: BigUint,
+    pub record: BigUint,
+}
+
+impl Race {
+    fn wins(&self, hold: &BigUint) -> bool {
+        hold <= &self.time && hold * (&self.time - hold) > self.record
+    }
+
+    /// Hold times beating the record, from the roots of `hold * (time - hold) = record`.
+    ///
+    /// Holds that exactly match the record do not win, so the bounds are moved inwards when a root
+    /// is an integer.
+    #[must_use]
+    pub fn winning_holds(&self) -> Option<RangeInclusive<BigUint>> {
+        let square = &self.time * &self.time;
+        let four_records = &self.record * 4u32;
+        if square < four_records {
+            return None;
+        }
+        let discriminant = square - four_records;
+        let half = &self.time / 2u32;
+
+        let mut low = (&self.time - discriminant.sqrt()) / 2u32;
+        while low > BigUint::ZERO && self.wins(&(&low - 1u32)) {
+            low -= 1u32;
+        }
+        while low <= half && !self.wins(&low) {
+            low += 1u32;
+        }
+        if low > half {
+            return None;
+        }
+
+        let high = &self.time - &low;
+        Some(low..=high)
+    }
+
+    #[must_use]
+    pub fn runs(&self) -> BigUint {
+        self.winning_holds()
+            .map_or(BigUint::ZERO, |holds| holds.end() - holds.start() + 1u32)
+    }
+}
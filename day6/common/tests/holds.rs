@@ -0,0 +1,56 @@
+use day6common::Race;
+use num_bigint::BigUint;
+use std::ops::RangeInclusive;
+
+fn race(time: u64, record: u64) -> Race {
+    Race {
+        time: time.into(),
+        record: record.into(),
+    }
+}
+
+fn holds(time: u64, record: u64) -> Option<RangeInclusive<u64>> {
+    let holds = race(time, record).winning_holds()?;
+    let bound = |value: &BigUint| u64::try_from(value).unwrap();
+    Some(bound(holds.start())..=bound(holds.end()))
+}
+
+#[test]
+fn example_races() {
+    assert_eq!(holds(7, 9), Some(2..=5));
+    assert_eq!(holds(15, 40), Some(4..=11));
+    assert_eq!(holds(30, 200), Some(11..=19));
+    assert_eq!(race(71530, 940_200).runs(), BigUint::from(71503u32));
+}
+
+#[test]
+fn unbeatable_records_have_no_holds() {
+    assert_eq!(holds(30, 226), None);
+    assert_eq!(holds(30, 225), None);
+    assert_eq!(race(30, 1000).runs(), BigUint::ZERO);
+}
+
+#[test]
+fn zero_time_has_no_runs() {
+    assert_eq!(holds(0, 0), None);
+    assert_eq!(race(0, 0).runs(), BigUint::ZERO);
+}
+
+#[test]
+fn integer_roots_are_excluded() {
+    // 3 * 7 = 21 and 10^2 - 4 * 21 = 16, so holds of exactly 3 and 7 only tie the record.
+    assert_eq!(holds(10, 21), Some(4..=6));
+    assert_eq!(holds(10, 24), Some(5..=5));
+    assert_eq!(holds(10, 25), None);
+}
+
+#[test]
+fn agrees_with_counting_every_hold() {
+    for time in 0..40 {
+        for record in 0..time * time / 4 + 2 {
+            let expected: Vec<u64> = (0..=time).filter(|hold| hold * (time - hold) > record).collect();
+            let found: Vec<u64> = holds(time, record).into_iter().flatten().collect();
+            assert_eq!(found, expected, "time {time}, record {record}");
+        }
+    }
+}
@@ -1,19 +1,8 @@
+use day6common::Race;
+use num_bigint::BigUint;
 use std::error::Error;
 use std::io::{stdin, BufRead};
 use std::iter;
-#[derive(Debug)]
-struct Race {
-    time: u64,
-    record: u64,
-}
-
-impl Race {
-    fn runs(&self) -> usize {
-        (0..=self.time)
-            .filter(|time| (self.time - time) * time > self.record)
-            .count()
-    }
-}
 
 fn main() -> Result<(), Box<dyn Error>> {
     let lines = stdin().lock().lines();
@@ -24,21 +13,21 @@ fn main() -> Result<(), Box<dyn Error>> {
         .ok_or("no times given")?
         .split_whitespace()
         .skip(1)
-        .map(str::parse::<u64>)
+        .map(str::parse::<BigUint>)
         .collect::<Result<Vec<_>, _>>()?;
     let records = lines
         .next()
         .ok_or("no distances given")?
         .split_whitespace()
         .skip(1)
-        .map(str::parse::<u64>)
+        .map(str::parse::<BigUint>)
         .collect::<Result<Vec<_>, _>>()?;
 
     let races = iter::zip(times, records)
         .map(|(time, record)| Race { time, record })
         .collect::<Vec<_>>();
 
-    let possibilities = races.iter().map(Race::runs).product::<usize>();
+    let possibilities = races.iter().map(Race::runs).product::<BigUint>();
     println!("{possibilities}");
 
     Ok::<_, Box<dyn Error>>(())
@@ -0,0 +1,433 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Card {
+    pub label: char,
+    pub suit: Option<char>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    FiveOfAKind,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rules {
+    /// Card labels, weakest first.
+    pub order: Vec<char>,
+    /// Labels that stand in for whichever card makes the best hand, while keeping their own rank
+    /// for tie-breaks.
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+    /// Categories in play, weakest first. A hand takes the strongest one it satisfies.
+    pub categories: Vec<Category>,
+    /// Whether cards are written as a label followed by a suit, e.g. `Kh`.
+    pub suited: bool,
+}
+
+impl Rules {
+    #[must_use]
+    pub fn camel() -> Self {
+        Rules {
+            order: "23456789TJQKA".chars().collect(),
+            wildcards: Vec::new(),
+            hand_size: 5,
+            categories: vec![
+                Category::HighCard,
+                Category::OnePair,
+                Category::TwoPair,
+                Category::ThreeOfAKind,
+                Category::FullHouse,
+                Category::FourOfAKind,
+                Category::FiveOfAKind,
+            ],
+            suited: false,
+        }
+    }
+
+    #[must_use]
+    pub fn camel_jokers() -> Self {
+        Rules {
+            order: "J23456789TQKA".chars().collect(),
+            wildcards: vec!['J'],
+            ..Self::camel()
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Fails on unknown labels, missing suits or a hand of the wrong size.
+    pub fn parse_hand(&self, hand: &str) -> Result<Vec<Card>, String> {
+        let mut chars = hand.chars();
+        let mut cards = Vec::with_capacity(self.hand_size);
+        while let Some(label) = chars.next() {
+            if !self.order.contains(&label) {
+                return Err(format!("No such card: {label}"));
+            }
+            let suit = if self.suited {
+                Some(
+                    chars
+                        .next()
+                        .ok_or_else(|| format!("missing suit after {label} in {hand}"))?,
+                )
+            } else {
+                None
+            };
+            cards.push(Card { label, suit });
+        }
+        if cards.len() != self.hand_size {
+            return Err(format!("expected {} cards in {hand}", self.hand_size));
+        }
+        Ok(cards)
+    }
+
+    fn rank(&self, card: Card) -> usize {
+        self.order
+            .iter()
+            .position(|&label| label == card.label)
+            .unwrap_or_default()
+    }
+
+    fn strength(&self, category: Category) -> usize {
+        self.categories.iter().position(|&c| c == category).unwrap_or_default()
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        self.wildcards.contains(&card.label)
+    }
+
+    /// Strongest category of a hand whose wildcards, if any, have already been substituted.
+    fn category_of(&self, hand: &[Card]) -> Category {
+        let mut ranks = hand.iter().map(|&card| self.rank(card)).collect::<Vec<_>>();
+        ranks.sort_unstable();
+        let mut counts = ranks.chunk_by(|a, b| a == b).map(<[usize]>::len).collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let (first, second) = (
+            counts.first().copied().unwrap_or_default(),
+            counts.get(1).copied().unwrap_or_default(),
+        );
+
+        let straight = || ranks.windows(2).all(|pair| pair[1] == pair[0] + 1);
+        let flush = || self.suited && hand.windows(2).all(|pair| pair[0].suit == pair[1].suit);
+
+        self.categories
+            .iter()
+            .rev()
+            .copied()
+            .find(|category| match category {
+                Category::HighCard => true,
+                Category::OnePair => first >= 2,
+                Category::TwoPair => first >= 2 && second >= 2,
+                Category::ThreeOfAKind => first >= 3,
+                Category::Straight => straight(),
+                Category::Flush => flush(),
+                Category::FullHouse => first >= 3 && second >= 2,
+                Category::FourOfAKind => first >= 4,
+                Category::StraightFlush => straight() && flush(),
+                Category::FiveOfAKind => first >= 5,
+            })
+            .unwrap_or(Category::HighCard)
+    }
+
+    /// Cards wildcards may turn into: the cards already held, or any card when straights are in play.
+    fn substitutes(&self, hand: &[Card]) -> Vec<Card> {
+        let held = hand
+            .iter()
+            .copied()
+            .filter(|&card| !self.is_wild(card))
+            .collect::<Vec<_>>();
+        let straights = self
+            .categories
+            .iter()
+            .any(|c| matches!(c, Category::Straight | Category::StraightFlush));
+
+        let mut substitutes = if straights || held.is_empty() {
+            let mut suits = held.iter().map(|card| card.suit).collect::<Vec<_>>();
+            if suits.is_empty() {
+                suits.extend(hand.first().map(|card| card.suit));
+            }
+            // wildcard labels stay in, as a wildcard may be needed as itself to fill a straight
+            self.order
+                .iter()
+                .flat_map(|&label| suits.iter().map(move |&suit| Card { label, suit }))
+                .collect()
+        } else {
+            held
+        };
+        substitutes.sort_unstable_by_key(|&card| (self.rank(card), card.suit));
+        substitutes.dedup();
+        substitutes
+    }
+
+    /// Best category reachable by substituting wildcards. Among equally strong substitutions the
+    /// one using the highest cards is kept.
+    #[must_use]
+    pub fn classify(&self, hand: &[Card]) -> Classification {
+        let wild = hand.iter().filter(|&&card| self.is_wild(card)).count();
+        let substitutes = if wild == 0 { Vec::new() } else { self.substitutes(hand) };
+        if substitutes.is_empty() {
+            return Classification {
+                category: self.category_of(hand),
+                substituted: hand.to_vec(),
+            };
+        }
+
+        let mut choice = vec![0; wild];
+        let mut best: Option<Classification> = None;
+        loop {
+            let mut choices = choice.iter();
+            let candidate = hand
+                .iter()
+                .map(|&card| {
+                    if self.is_wild(card) {
+                        choices.next().map_or(card, |&i| substitutes[i])
+                    } else {
+                        card
+                    }
+                })
+                .collect::<Vec<_>>();
+            let category = self.category_of(&candidate);
+            if best
+                .as_ref()
+                .is_none_or(|best| self.strength(category) >= self.strength(best.category))
+            {
+                best = Some(Classification {
+                    category,
+                    substituted: candidate,
+                });
+            }
+
+            // next multiset of substitutes, as non-decreasing index sequences
+            let Some(position) = choice.iter().rposition(|&i| i + 1 < substitutes.len()) else {
+                return best.unwrap_or_else(|| unreachable!());
+            };
+            let next = choice[position] + 1;
+            choice[position..].fill(next);
+        }
+    }
+
+    /// Category strength first, then each card's rank in order of appearance.
+    #[must_use]
+    pub fn key(&self, hand: &[Card]) -> (usize, Vec<usize>) {
+        (
+            self.strength(self.classify(hand).category),
+            hand.iter().map(|&card| self.rank(card)).collect(),
+        )
+    }
+
+    /// Orders hands by [`Rules::key`]: every hand, high cards included, has a category, so the
+    /// ordering is total on keys. Hands only tie when they hold the same labels in the same order,
+    /// which can differ by suit alone.
+    #[must_use]
+    pub fn cmp(&self, hand: &[Card], other: &[Card]) -> Ordering {
+        self.key(hand).cmp(&self.key(other))
+    }
+
+    /// Same ordering as [`Rules::cmp`], along with the step that decided it.
+    #[must_use]
+    pub fn explain_cmp(&self, hand: &[Card], other: &[Card]) -> Comparison {
+        let ours = self.classify(hand);
+        let theirs = self.classify(other);
+
+        let (ordering, decision) = match self.strength(ours.category).cmp(&self.strength(theirs.category)) {
+            Ordering::Equal => hand
+                .iter()
+                .zip(other)
+                .enumerate()
+                .map(|(position, (&ours, &theirs))| {
+                    (
+                        self.rank(ours).cmp(&self.rank(theirs)),
+                        Decision::Card { position, ours, theirs },
+                    )
+                })
+                .find(|(ordering, _)| ordering.is_ne())
+                .unwrap_or((hand.len().cmp(&other.len()), Decision::Identical)),
+            ordering => (ordering, Decision::Category),
+        };
+
+        Comparison {
+            ordering,
+            decision,
+            ours,
+            theirs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Classification {
+    pub category: Category,
+    /// The hand with each wildcard replaced by the card it stands for.
+    pub substituted: Vec<Card>,
+}
+
+impl Classification {
+    /// Positions of substituted wildcards, with the card each one became.
+    pub fn substitutions<'a>(&'a self, hand: &'a [Card]) -> impl Iterator<Item = (usize, Card)> + 'a {
+        hand.iter()
+            .zip(&self.substituted)
+            .enumerate()
+            .filter(|(_, (original, substituted))| original != substituted)
+            .map(|(position, (_, &substituted))| (position, substituted))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Category,
+    /// First position where the cards differ in rank. Wildcards compare by their own rank.
+    Card {
+        position: usize,
+        ours: Card,
+        theirs: Card,
+    },
+    Identical,
+}
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub ordering: Ordering,
+    pub decision: Decision,
+    pub ours: Classification,
+    pub theirs: Classification,
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)?;
+        if let Some(suit) = self.suit {
+            write!(f, "{suit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Classification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} (as ", self.category)?;
+        for card in &self.substituted {
+            write!(f, "{card}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.decision {
+            Decision::Category => write!(f, "{:?}: {} vs {}", self.ordering, self.ours, self.theirs),
+            Decision::Card { position, ours, theirs } => write!(
+                f,
+                "{:?}: both {}, card {} is {ours} vs {theirs}",
+                self.ordering,
+                self.ours,
+                position + 1
+            ),
+            Decision::Identical => write!(f, "{:?}: identical hands", self.ordering),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub player: String,
+    pub hand: Vec<Card>,
+    pub bid: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Ties {
+    /// Tied hands all take the lowest rank of their group, and the next hand skips past them.
+    SharedRank,
+    /// Tied hands are ranked in the order they were entered, earlier ones ranking lower.
+    EntryOrder,
+}
+
+#[derive(Clone, Debug)]
+pub struct Placement {
+    pub entry: Entry,
+    /// One-based, the weakest hand of a round ranks 1.
+    pub rank: usize,
+    pub tied: bool,
+    pub winnings: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tournament {
+    rules: Rules,
+    ties: Ties,
+    rounds: Vec<Vec<Placement>>,
+    totals: HashMap<String, usize>,
+}
+
+impl Tournament {
+    #[must_use]
+    pub fn new(rules: Rules, ties: Ties) -> Self {
+        Tournament {
+            rules,
+            ties,
+            rounds: Vec::new(),
+            totals: HashMap::new(),
+        }
+    }
+
+    /// Ranks one round of entries and pays each `bid * rank`, weakest hand first.
+    pub fn play(&mut self, entries: Vec<Entry>) -> &[Placement] {
+        let mut keyed = entries
+            .into_iter()
+            .map(|entry| (self.rules.key(&entry.hand), entry))
+            .collect::<Vec<_>>();
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut placements = Vec::with_capacity(keyed.len());
+        let mut start = 0;
+        for group in keyed.chunk_by(|(a, _), (b, _)| a == b) {
+            let tied = group.len() > 1;
+            for (offset, (_, entry)) in group.iter().enumerate() {
+                let rank = match self.ties {
+                    Ties::SharedRank => start + 1,
+                    Ties::EntryOrder => start + offset + 1,
+                };
+                *self.totals.entry(entry.player.clone()).or_default() += entry.bid * rank;
+                placements.push(Placement {
+                    entry: entry.clone(),
+                    rank,
+                    tied,
+                    winnings: entry.bid * rank,
+                });
+            }
+            start += group.len();
+        }
+
+        self.rounds.push(placements);
+        self.rounds.last().map_or(&[], Vec::as_slice)
+    }
+
+    #[must_use]
+    pub fn rounds(&self) -> &[Vec<Placement>] {
+        &self.rounds
+    }
+
+    /// Cumulative winnings per player, highest first.
+    #[must_use]
+    pub fn standings(&self) -> Vec<(&str, usize)> {
+        let mut standings = self
+            .totals
+            .iter()
+            .map(|(player, &winnings)| (player.as_str(), winnings))
+            .collect::<Vec<_>>();
+        standings.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        standings
+    }
+}
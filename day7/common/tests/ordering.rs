@@ -0,0 +1,143 @@
+use day7common::{Card, Category, Rules};
+
+fn all_hands(rules: &Rules) -> Vec<Vec<Card>> {
+    (0..rules.hand_size).fold(vec![Vec::new()], |hands, _| {
+        hands
+            .iter()
+            .flat_map(|hand| {
+                rules.order.iter().map(|&label| {
+                    let mut hand = hand.clone();
+                    hand.push(Card { label, suit: None });
+                    hand
+                })
+            })
+            .collect()
+    })
+}
+
+/// Camel Cards hand type from the label counts alone, from 0 for a high card to 6 for five of a
+/// kind.
+fn reference_type(labels: &[char]) -> usize {
+    let mut counts = labels
+        .iter()
+        .map(|label| labels.iter().filter(|&other| other == label).count())
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5, ..] => 6,
+        [4, ..] => 5,
+        [3, 3, 3, 2, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, 2, 2, 1] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+/// Best [`reference_type`] over every way of replacing each joker with another label.
+fn reference_joker_type(labels: &[char]) -> usize {
+    let Some(joker) = labels.iter().position(|&label| label == 'J') else {
+        return reference_type(labels);
+    };
+    "23456789TQKA"
+        .chars()
+        .map(|label| {
+            let mut labels = labels.to_vec();
+            labels[joker] = label;
+            reference_joker_type(&labels)
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// Compares `rules` against the reference ordering: type first, then labels left to right by
+/// their position in `order`.
+fn assert_matches_reference(rules: &Rules, order: &str, hand_type: fn(&[char]) -> usize) {
+    let hands = all_hands(rules);
+    assert_eq!(hands.len(), 13usize.pow(5));
+    let keys = hands
+        .iter()
+        .map(|hand| {
+            let labels = hand.iter().map(|card| card.label).collect::<Vec<_>>();
+            let ranks = labels
+                .iter()
+                .map(|&label| order.find(label).unwrap_or_else(|| unreachable!()))
+                .collect::<Vec<_>>();
+            (hand_type(&labels), ranks)
+        })
+        .collect::<Vec<_>>();
+
+    // pairs a fixed stride apart in enumeration order, so they differ in one or several positions
+    for stride in [0, 1, 12, 13, 169, 2_197, 28_561, 100_003] {
+        for i in (0..hands.len()).step_by(7) {
+            let j = (i + stride) % hands.len();
+            let expected = keys[i].cmp(&keys[j]);
+            let (a, b) = (&hands[i], &hands[j]);
+            assert_eq!(rules.cmp(a, b), expected, "{a:?} vs {b:?}");
+            assert_eq!(rules.explain_cmp(a, b).ordering, expected, "{a:?} vs {b:?}");
+        }
+    }
+}
+
+#[test]
+fn camel_ordering_matches_reference() {
+    let rules = Rules::camel();
+    assert_matches_reference(&rules, "23456789TJQKA", reference_type);
+    let hands = all_hands(&rules);
+
+    let categories = hands
+        .iter()
+        .map(|hand| rules.classify(hand).category)
+        .collect::<Vec<_>>();
+    let count = |category| categories.iter().filter(|&&c| c == category).count();
+    assert_eq!(count(Category::HighCard), 154_440);
+    assert_eq!(count(Category::OnePair), 171_600);
+    assert_eq!(count(Category::TwoPair), 25_740);
+    assert_eq!(count(Category::ThreeOfAKind), 17_160);
+    assert_eq!(count(Category::FullHouse), 1_560);
+    assert_eq!(count(Category::FourOfAKind), 780);
+    assert_eq!(count(Category::FiveOfAKind), 13);
+}
+
+#[test]
+fn joker_ordering_matches_brute_force_and_is_never_weaker() {
+    let camel = Rules::camel();
+    let jokers = Rules::camel_jokers();
+    assert_matches_reference(&jokers, "J23456789TQKA", reference_joker_type);
+    let hands = all_hands(&jokers);
+
+    let strength = |category| camel.categories.iter().position(|&c| c == category);
+    for hand in &hands {
+        let plain = camel.classify(hand);
+        let wild = jokers.classify(hand);
+        if hand.iter().any(|card| card.label == 'J') {
+            assert!(strength(wild.category) >= strength(plain.category), "{hand:?}");
+        } else {
+            assert_eq!(wild, plain, "{hand:?}");
+        }
+        assert_eq!(camel.classify(&wild.substituted).category, wild.category, "{hand:?}");
+    }
+}
+
+#[test]
+fn wildcard_can_stand_for_its_own_label() {
+    let rules = Rules {
+        order: "23456789TJQKA".chars().collect(),
+        wildcards: vec!['J'],
+        categories: vec![
+            Category::HighCard,
+            Category::OnePair,
+            Category::TwoPair,
+            Category::ThreeOfAKind,
+            Category::Straight,
+            Category::Flush,
+            Category::FullHouse,
+            Category::FourOfAKind,
+            Category::StraightFlush,
+        ],
+        suited: true,
+        ..Rules::camel()
+    };
+    let hand = rules.parse_hand("AhKhQhJhTh").unwrap();
+    assert_eq!(rules.classify(&hand).category, Category::StraightFlush);
+}
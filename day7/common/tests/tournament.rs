@@ -0,0 +1,126 @@
+use day7common::{Entry, Rules, Ties, Tournament};
+
+fn entries(rules: &Rules, round: &[(&str, &str, usize)]) -> Vec<Entry> {
+    round
+        .iter()
+        .map(|&(player, hand, bid)| Entry {
+            player: player.to_string(),
+            hand: rules.parse_hand(hand).unwrap(),
+            bid,
+        })
+        .collect()
+}
+
+/// `(player, rank, tied, winnings)` of each placement, weakest first.
+fn play(tournament: &mut Tournament, entries: Vec<Entry>) -> Vec<(String, usize, bool, usize)> {
+    tournament
+        .play(entries)
+        .iter()
+        .map(|placement| {
+            (
+                placement.entry.player.clone(),
+                placement.rank,
+                placement.tied,
+                placement.winnings,
+            )
+        })
+        .collect()
+}
+
+const EXAMPLE: [(&str, &str, usize); 5] = [
+    ("ann", "32T3K", 765),
+    ("bob", "T55J5", 684),
+    ("cat", "KK677", 28),
+    ("dan", "KTJJT", 220),
+    ("eve", "QQQJA", 483),
+];
+
+const TIED: [(&str, &str, usize); 3] = [("ann", "AAAAA", 10), ("bob", "AAAAA", 20), ("cat", "23456", 5)];
+
+fn placement(player: &str, rank: usize, tied: bool, winnings: usize) -> (String, usize, bool, usize) {
+    (player.to_string(), rank, tied, winnings)
+}
+
+#[test]
+fn example_round() {
+    let rules = Rules::camel();
+    let mut tournament = Tournament::new(rules.clone(), Ties::SharedRank);
+    assert_eq!(
+        play(&mut tournament, entries(&rules, &EXAMPLE)),
+        [
+            placement("ann", 1, false, 765),
+            placement("dan", 2, false, 440),
+            placement("cat", 3, false, 84),
+            placement("bob", 4, false, 2736),
+            placement("eve", 5, false, 2415),
+        ]
+    );
+    let total = tournament
+        .standings()
+        .iter()
+        .map(|&(_, winnings)| winnings)
+        .sum::<usize>();
+    assert_eq!(total, 6440);
+}
+
+#[test]
+fn tied_hands_share_the_lowest_rank() {
+    let rules = Rules::camel();
+    let mut tournament = Tournament::new(rules.clone(), Ties::SharedRank);
+    assert_eq!(
+        play(&mut tournament, entries(&rules, &TIED)),
+        [
+            placement("cat", 1, false, 5),
+            placement("ann", 2, true, 20),
+            placement("bob", 2, true, 40),
+        ]
+    );
+}
+
+#[test]
+fn tied_hands_rank_in_entry_order() {
+    let rules = Rules::camel();
+    let mut tournament = Tournament::new(rules.clone(), Ties::EntryOrder);
+    assert_eq!(
+        play(&mut tournament, entries(&rules, &TIED)),
+        [
+            placement("cat", 1, false, 5),
+            placement("ann", 2, true, 20),
+            placement("bob", 3, true, 60),
+        ]
+    );
+
+    let mut reversed = TIED;
+    reversed.swap(0, 1);
+    let mut tournament = Tournament::new(rules.clone(), Ties::EntryOrder);
+    assert_eq!(
+        play(&mut tournament, entries(&rules, &reversed))[1..],
+        [placement("bob", 2, true, 40), placement("ann", 3, true, 30)]
+    );
+}
+
+#[test]
+fn standings_total_every_round() {
+    let rules = Rules::camel();
+    let mut tournament = Tournament::new(rules.clone(), Ties::SharedRank);
+    play(&mut tournament, entries(&rules, &EXAMPLE));
+    play(&mut tournament, entries(&rules, &TIED));
+    assert_eq!(tournament.rounds().len(), 2);
+    assert_eq!(tournament.rounds()[0].len(), 5);
+    assert_eq!(
+        tournament.standings(),
+        [("bob", 2776), ("eve", 2415), ("ann", 785), ("dan", 440), ("cat", 89)]
+    );
+}
+
+#[test]
+fn equal_totals_are_listed_by_name() {
+    let rules = Rules::camel();
+    let mut tournament = Tournament::new(rules.clone(), Ties::SharedRank);
+    play(
+        &mut tournament,
+        entries(&rules, &[("zed", "AAAAA", 3), ("amy", "AAAAA", 3)]),
+    );
+    assert_eq!(tournament.standings(), [("amy", 3), ("zed", 3)]);
+    assert!(Tournament::new(rules, Ties::EntryOrder).standings().is_empty());
+}
@@ -1,154 +1,85 @@
-use sort_by_derive::{EnumSequence, SortBy};
-use std::cmp::Ordering;
+use day7common::{Card, Entry, Rules, Ties, Tournament};
+use std::env::args;
 use std::error::Error;
 use std::io::{stdin, BufRead};
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
-enum Card {
-    J,
-    Card2,
-    Card3,
-    Card4,
-    Card5,
-    Card6,
-    Card7,
-    Card8,
-    Card9,
-    T,
-    Q,
-    K,
-    A,
+fn show(hand: &[Card]) -> String {
+    hand.iter().map(ToString::to_string).collect()
 }
 
-impl TryFrom<char> for Card {
-    type Error = String;
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        Ok(match c {
-            'A' => Self::A,
-            'K' => Self::K,
-            'Q' => Self::Q,
-            'J' => Self::J,
-            'T' => Self::T,
-            '9' => Self::Card9,
-            '8' => Self::Card8,
-            '7' => Self::Card7,
-            '6' => Self::Card6,
-            '5' => Self::Card5,
-            '4' => Self::Card4,
-            '3' => Self::Card3,
-            '2' => Self::Card2,
-            _ => Err(format!("No such card: {c}"))?,
-        })
-    }
-}
-
-type Hand = [Card; 5];
-
-#[derive(Copy, Clone, EnumSequence, SortBy, Debug)]
-#[sort_by(enum_sequence())]
-enum HandType {
-    Card(Card),
-    Pair(Card),
-    TwoPair(Card, Card),
-    ThreeOfAKind(Card),
-    FullHouse(Card, Card),
-    FourOfAKind(Card),
-    FiveOfAKind(Card),
-}
-
-trait CmpHand {
-    fn cmp(&self, other: &Self) -> Ordering;
-}
-
-impl CmpHand for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match (HandType::try_from(*self), HandType::try_from(*other)) {
-            (Ok(self_hand), Ok(other_hand)) => self_hand.cmp(&other_hand).then_with(|| Ord::cmp(self, other)),
-            (Ok(_), _) => Ordering::Greater,
-            (_, Ok(_)) => Ordering::Less,
-            (Err(()), Err(())) => Ord::cmp(self, other),
+/// Rounds are separated by blank lines, each line being `<player> <hand> <bid>`.
+fn tournament(lines: impl Iterator<Item = String>, rules: &Rules) -> Result<(), Box<dyn Error>> {
+    let mut tournament = Tournament::new(rules.clone(), Ties::SharedRank);
+    let mut lines = lines.peekable();
+    while lines.peek().is_some() {
+        let entries = lines
+            .by_ref()
+            .take_while(|line| !line.is_empty())
+            .map(|line| {
+                let mut parts = line.split_whitespace();
+                let (Some(player), Some(cards), Some(bid), None) =
+                    (parts.next(), parts.next(), parts.next(), parts.next())
+                else {
+                    return Err(format!("expected `<player> <hand> <bid>`, got {line}").into());
+                };
+                Ok(Entry {
+                    player: player.to_string(),
+                    hand: rules.parse_hand(cards)?,
+                    bid: bid.parse()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+        let round = tournament.rounds().len() + 1;
+        for placement in tournament.play(entries) {
+            println!(
+                "round {round}: #{} {} {} bid {} won {}{}",
+                placement.rank,
+                placement.entry.player,
+                show(&placement.entry.hand),
+                placement.entry.bid,
+                placement.winnings,
+                if placement.tied { " (tied)" } else { "" }
+            );
         }
     }
-}
 
-impl TryFrom<Hand> for HandType {
-    type Error = ();
-
-    fn try_from(hand: Hand) -> Result<Self, Self::Error> {
-        let mut hand = hand.to_vec();
-        hand.retain(|card| *card != Card::J);
-        let js = 5 - hand.len();
-        if js == 5 {
-            return Ok(HandType::FiveOfAKind(Card::J));
-        }
-
-        hand.sort_unstable();
-
-        let mut groups = hand.into_iter().fold(Vec::<HandType>::new(), |mut groups, next_card| {
-            match groups.last().copied() {
-                Some(HandType::FourOfAKind(card)) if card == next_card => {
-                    *groups.last_mut().unwrap() = HandType::FiveOfAKind(card);
-                }
-                Some(HandType::ThreeOfAKind(card)) if card == next_card => {
-                    *groups.last_mut().unwrap() = HandType::FourOfAKind(card);
-                }
-                Some(HandType::Pair(card)) if card == next_card => {
-                    *groups.last_mut().unwrap() = HandType::ThreeOfAKind(card);
-                }
-                Some(HandType::Card(card)) if card == next_card => {
-                    *groups.last_mut().unwrap() = HandType::Pair(card);
-                }
-                _ => groups.push(HandType::Card(next_card)),
-            };
-            groups
-        });
-        groups.sort_unstable();
-        let hand_type = match (groups.pop().unwrap(), groups.pop()) {
-            (HandType::ThreeOfAKind(highest), Some(HandType::Pair(second))) => HandType::FullHouse(highest, second),
-            (HandType::Pair(highest), Some(HandType::Pair(second))) => HandType::TwoPair(highest, second),
-            (hand, _) => hand,
-        };
-
-        let hand_type = (0..js).fold(hand_type, |hand_type, _| {
-            let up = match hand_type {
-                HandType::Card(x) => HandType::Pair(x),
-                HandType::Pair(x) => HandType::ThreeOfAKind(x),
-                HandType::TwoPair(x, y) => HandType::FullHouse(x, y),
-                HandType::ThreeOfAKind(x) => HandType::FourOfAKind(x),
-                HandType::FourOfAKind(x) => HandType::FiveOfAKind(x),
-                HandType::FiveOfAKind(_) | HandType::FullHouse(_, _) => unreachable!(),
-            };
-            println!("Up: {hand_type:?} {up:?}");
-            up
-        });
-        match hand_type {
-            HandType::Card(_) => Err(()),
-            _ => Ok(hand_type),
-        }
+    for (player, winnings) in tournament.standings() {
+        println!("{player}: {winnings}");
     }
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let lines = stdin().lock().lines();
     let lines = lines.map_while(Result::ok);
+    let rules = Rules::camel_jokers();
+
+    if args().any(|arg| arg == "--tournament") {
+        return tournament(lines, &rules);
+    }
 
     let mut game = lines.into_iter().try_fold(Vec::new(), |mut result, line| {
         let (cards, bid) = line
             .split_once(' ')
             .ok_or_else(|| format!("missing separator {line}"))?;
-        let hand = Hand::try_from(
-            cards
-                .chars()
-                .map(Card::try_from)
-                .collect::<Result<Vec<_>, _>>()?
-                .as_slice(),
-        )?;
-        result.push((hand, bid.parse::<usize>()?));
+        result.push((rules.parse_hand(cards)?, bid.parse::<usize>()?));
         Ok::<_, Box<dyn Error>>(result)
     })?;
 
-    game.sort_by(|hand1, hand2| CmpHand::cmp(&hand1.0, &hand2.0));
+    game.sort_by_cached_key(|(hand, _)| rules.key(hand));
+
+    if args().any(|arg| arg == "--explain") {
+        for pair in game.windows(2) {
+            let (lower, higher) = (&pair[0].0, &pair[1].0);
+            eprintln!(
+                "{} vs {}: {}",
+                show(higher),
+                show(lower),
+                rules.explain_cmp(higher, lower)
+            );
+        }
+    }
 
     let sum = game
         .into_iter()
@@ -0,0 +1,67 @@
+use benchkit::{rate, XorShift};
+use day8common::{Direction, Network};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+
+const NODES: u32 = 2_000_000;
+const STEPS: u32 = 20_000_000;
+
+fn name(id: u64) -> String {
+    format!("N{id:07}")
+}
+
+fn main() {
+    let mut rng = XorShift::new(0x2023_1208);
+    let directions = (0..281)
+        .map(|_| {
+            if rng.next_below(2) == 0 {
+                Direction::L
+            } else {
+                Direction::R
+            }
+        })
+        .collect::<Vec<_>>();
+    let links = (0..NODES)
+        .map(|id| {
+            (
+                name(u64::from(id)),
+                name(rng.next_below(u64::from(NODES))),
+                name(rng.next_below(u64::from(NODES))),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let mut network = Network::new(directions.clone());
+    for (node, left, right) in &links {
+        network.add(node, left, right).unwrap_or_else(|err| panic!("{err}"));
+    }
+    println!("{:>16}: {:>10.2?} for {NODES} nodes", "intern", start.elapsed());
+
+    let origin = network.id(&name(0)).unwrap_or_default();
+    let start = Instant::now();
+    black_box(network.walk(origin, |node| black_box(node) == usize::MAX, u64::from(STEPS)));
+    println!("{:>16}: {}", "indices", rate(STEPS, "steps", start));
+
+    let by_name = links
+        .iter()
+        .map(|(node, left, right)| (node.as_str(), (left.as_str(), right.as_str())))
+        .collect::<HashMap<_, _>>();
+    let start = Instant::now();
+    let origin = name(0);
+    let mut node = origin.as_str();
+    for direction in directions
+        .iter()
+        .cycle()
+        .take(usize::try_from(STEPS).unwrap_or_default())
+    {
+        let (left, right) = by_name[node];
+        node = match direction {
+            Direction::L => left,
+            Direction::R => right,
+        };
+    }
+    black_box(node);
+    println!("{:>16}: {}", "string hashing", rate(STEPS, "steps", start));
+}
@@ -0,0 +1,461 @@
+use fnv::FnvHashMap;
+use std::collections::HashMap;
+use std::env::args;
+use std::error::Error;
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Direction {
+    L,
+    R,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = &'static str;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'L' => Direction::L,
+            'R' => Direction::R,
+            _ => Err("invalid direction")?,
+        })
+    }
+}
+
+fn identifier(name: &str) -> Option<&str> {
+    let name = name.trim();
+    (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')).then_some(name)
+}
+
+fn parse_node(line: &str) -> Option<(&str, &str, &str)> {
+    let (name, destinations) = line.split_once('=')?;
+    let (left, right) = destinations
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(',')?;
+    Some((identifier(name)?, identifier(left)?, identifier(right)?))
+}
+
+/// Picks start or end nodes by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    Suffix(String),
+    Prefix(String),
+    Names(Vec<String>),
+}
+
+impl Selector {
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Selector::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Selector::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            Selector::Names(names) => names.iter().any(|n| n == name),
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    /// Accepts `suffix:Z`, `prefix:A` or `names:AAA,BBB`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.split_once(':') {
+            Some(("suffix", suffix)) => Selector::Suffix(suffix.to_string()),
+            Some(("prefix", prefix)) => Selector::Prefix(prefix.to_string()),
+            Some(("names", names)) => Selector::Names(names.split(',').map(str::to_string).collect()),
+            _ => Err(format!(
+                "invalid selector {s}, expected suffix:X, prefix:X or names:X,Y"
+            ))?,
+        })
+    }
+}
+
+/// Selector following `flag` on the command line, or `default` when the flag is absent.
+///
+/// # Errors
+///
+/// Fails when the flag has no value or the value is not a valid selector.
+pub fn selector(flag: &str, default: Selector) -> Result<Selector, String> {
+    let mut args = args().skip_while(|arg| arg != flag);
+    if args.next().is_none() {
+        return Ok(default);
+    }
+    args.next().ok_or_else(|| format!("{flag} expects a selector"))?.parse()
+}
+
+#[derive(Debug, Default)]
+pub struct Network {
+    pub directions: Vec<Direction>,
+    names: Vec<String>,
+    ids: FnvHashMap<String, usize>,
+    links: Vec<[usize; 2]>,
+    defined: Vec<bool>,
+}
+
+impl Network {
+    #[must_use]
+    pub fn new(directions: Vec<Direction>) -> Self {
+        Network {
+            directions,
+            ..Network::default()
+        }
+    }
+
+    /// Reads the directions line, then one `NAME = (LEFT, RIGHT)` node per non-blank line.
+    ///
+    /// # Errors
+    ///
+    /// Fails on a missing or invalid directions line, malformed node lines, nodes defined twice,
+    /// and nodes that are referenced but never defined.
+    pub fn parse(lines: impl IntoIterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let mut lines = lines.into_iter().zip(1..);
+        let directions = lines
+            .next()
+            .ok_or("expected directions line")?
+            .0
+            .trim()
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut network = Network::new(directions);
+        for (line, number) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, left, right) = parse_node(&line)
+                .ok_or_else(|| format!("line {number}: expected `NAME = (LEFT, RIGHT)`, got {line}"))?;
+            network
+                .add(name, left, right)
+                .map_err(|e| format!("line {number}: {e}"))?;
+        }
+        network.check()?;
+        Ok(network)
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.links.push([id, id]);
+        self.defined.push(false);
+        id
+    }
+
+    /// # Errors
+    ///
+    /// Fails if `name` was already defined.
+    pub fn add(&mut self, name: &str, left: &str, right: &str) -> Result<usize, String> {
+        if self.id(name).is_some_and(|id| self.defined[id]) {
+            return Err(format!("node {name} is defined twice"));
+        }
+        let (left, right) = (self.intern(left), self.intern(right));
+        let id = self.intern(name);
+        self.links[id] = [left, right];
+        self.defined[id] = true;
+        Ok(id)
+    }
+
+    /// # Errors
+    ///
+    /// Fails if a node was only ever seen as a destination.
+    pub fn check(&self) -> Result<(), String> {
+        match self.defined.iter().position(|&defined| !defined) {
+            Some(id) => Err(format!("node {} is referenced but never defined", self.names[id])),
+            None => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    #[must_use]
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Ids of the nodes `selector` matches.
+    pub fn select<'a>(&'a self, selector: &'a Selector) -> impl Iterator<Item = usize> + 'a {
+        (0..self.len()).filter(|&id| selector.matches(&self.names[id]))
+    }
+
+    #[must_use]
+    pub fn next(&self, node: usize, instruction: usize) -> usize {
+        match self.directions[instruction] {
+            Direction::L => self.links[node][0],
+            Direction::R => self.links[node][1],
+        }
+    }
+
+    /// Steps from `start` to the first end node, giving up after `limit` steps.
+    pub fn walk(&self, start: usize, is_end: impl Fn(usize) -> bool, limit: u64) -> Option<u64> {
+        let mut node = start;
+        let mut instructions = (0..self.directions.len()).cycle();
+        for step in 0..=limit {
+            if is_end(node) {
+                return Some(step);
+            }
+            node = self.next(node, instructions.next()?);
+        }
+        None
+    }
+
+    /// Walks from `start` until a `(node, instruction index)` state repeats, noting every step at
+    /// which the walk stands on an end node.
+    ///
+    /// # Errors
+    ///
+    /// Fails when there are no directions to follow.
+    pub fn analyse(&self, start: usize, is_end: impl Fn(usize) -> bool) -> Result<Cycle, String> {
+        if self.directions.is_empty() {
+            return Err("no directions to follow".to_string());
+        }
+
+        let mut seen = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut step = 0u64;
+        let mut instruction = 0;
+
+        let pre_period = loop {
+            if let Some(&first) = seen.get(&(node, instruction)) {
+                break first;
+            }
+            seen.insert((node, instruction), step);
+            if is_end(node) {
+                hits.push(step);
+            }
+            node = self.next(node, instruction);
+            instruction = (instruction + 1) % self.directions.len();
+            step += 1;
+        };
+
+        let split = hits.partition_point(|&hit| hit < pre_period);
+        Ok(Cycle {
+            pre_period,
+            period: step - pre_period,
+            cyclic_hits: hits.split_off(split),
+            transient_hits: hits,
+        })
+    }
+
+    /// Renders the network as Graphviz DOT: start nodes filled green, end nodes filled red, and the
+    /// edges of each walk's cycle drawn in that walk's colour.
+    ///
+    /// # Errors
+    ///
+    /// Fails when there are no directions to follow.
+    pub fn dot(&self, start: &Selector, end: &Selector) -> Result<String, String> {
+        let mut ghosts = HashMap::<(usize, usize), Vec<usize>>::new();
+        for (ghost, origin) in self.select(start).enumerate() {
+            let cycle = self.analyse(origin, |node| end.matches(&self.names[node]))?;
+            let mut node = origin;
+            for step in 0..cycle.pre_period + cycle.period {
+                let instruction = usize::try_from(step).unwrap_or_default() % self.directions.len();
+                let side = match self.directions[instruction] {
+                    Direction::L => 0,
+                    Direction::R => 1,
+                };
+                if step >= cycle.pre_period {
+                    let walkers = ghosts.entry((node, side)).or_default();
+                    if walkers.last() != Some(&ghost) {
+                        walkers.push(ghost);
+                    }
+                }
+                node = self.links[node][side];
+            }
+        }
+
+        let mut dot = String::from("digraph network {\n    node [style=filled, fillcolor=white];\n");
+        for name in &self.names {
+            let fill = match (start.matches(name), end.matches(name)) {
+                (true, true) => "\"green:red\"",
+                (true, false) => "green",
+                (false, true) => "red",
+                (false, false) => continue,
+            };
+            let _ = writeln!(dot, "    \"{name}\" [fillcolor={fill}];");
+        }
+        for (id, links) in self.links.iter().enumerate() {
+            for (side, label) in [(0, "L"), (1, "R")] {
+                let from = &self.names[id];
+                let to = &self.names[links[side]];
+                let _ = match ghosts.get(&(id, side)) {
+                    Some(walkers) => {
+                        let colours = walkers
+                            .iter()
+                            .map(|ghost| (ghost % 9 + 1).to_string())
+                            .collect::<Vec<_>>()
+                            .join(":");
+                        writeln!(
+                            dot,
+                            "    \"{from}\" -> \"{to}\" [label={label}, colorscheme=set19, color=\"{colours}\", penwidth=2];"
+                        )
+                    }
+                    None => writeln!(dot, "    \"{from}\" -> \"{to}\" [label={label}];"),
+                };
+            }
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+}
+
+/// Where a walk stands on end nodes: once at each transient hit, then at every
+/// `hit + k * period` for each cyclic hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps taken before entering the cycle.
+    pub pre_period: u64,
+    pub period: u64,
+    pub transient_hits: Vec<u64>,
+    pub cyclic_hits: Vec<u64>,
+}
+
+impl Cycle {
+    #[must_use]
+    pub fn hits(&self, step: u64) -> bool {
+        if step < self.pre_period {
+            return self.transient_hits.contains(&step);
+        }
+        let offset = self.pre_period + (step - self.pre_period) % self.period;
+        self.cyclic_hits.binary_search(&offset).is_ok()
+    }
+}
+
+/// Returns `(gcd, x, y)` with `a * x + b * y = gcd`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (divisor, x, y) = extended_gcd(b, a % b);
+        (divisor, y, x - (a / b) * y)
+    }
+}
+
+const OVERFLOW: &str = "first common hit overflows u64";
+
+/// Solves `n = a1 (mod m1)` and `n = a2 (mod m2)` for moduli that need not be coprime, giving
+/// `None` when there is no solution. Moduli stay within `u64`, as any answer must fit one.
+///
+/// # Errors
+///
+/// Fails when the combined modulus no longer fits in a `u64`.
+fn crt((a1, m1): (i128, i128), (a2, m2): (i128, i128)) -> Result<Option<(i128, i128)>, String> {
+    let (g, p, _) = extended_gcd(m1, m2);
+    if (a2 - a1) % g != 0 {
+        return Ok(None);
+    }
+    let lcm = (m1 / g)
+        .checked_mul(m2)
+        .filter(|&lcm| lcm <= i128::from(u64::MAX))
+        .ok_or(OVERFLOW)?;
+    // both factors are below m2 / g, so their product fits in a u128
+    let modulus = u128::try_from(m2 / g).map_err(|_| OVERFLOW)?;
+    let residue = u128::try_from(((a2 - a1) / g).rem_euclid(m2 / g)).map_err(|_| OVERFLOW)?;
+    let inverse = u128::try_from(p.rem_euclid(m2 / g)).map_err(|_| OVERFLOW)?;
+    let k = i128::try_from(residue * inverse % modulus).map_err(|_| OVERFLOW)?;
+    Ok(Some(((a1 + m1 * k).rem_euclid(lcm), lcm)))
+}
+
+/// Congruence combinations tried before [`first_common_hit`] falls back to stepping through hits.
+const COMBINATION_LIMIT: usize = 1 << 16;
+
+/// First step at which every walk stands on an end node at once.
+///
+/// # Errors
+///
+/// Fails when the walks never line up, or the answer does not fit in a `u64`.
+pub fn first_common_hit(cycles: &[Cycle]) -> Result<u64, String> {
+    let transient = cycles
+        .iter()
+        .flat_map(|cycle| cycle.transient_hits.iter().copied())
+        .filter(|&step| cycles.iter().all(|cycle| cycle.hits(step)))
+        .min();
+
+    // one congruence per choice of cyclic hit in each walk, with the latest hit as a lower bound
+    let mut combinations = HashMap::from([((0i128, 1i128), 0u64)]);
+    for cycle in cycles {
+        if combinations.len().saturating_mul(cycle.cyclic_hits.len()) > COMBINATION_LIMIT {
+            return search(cycles);
+        }
+        let mut next = HashMap::new();
+        for (&congruence, &floor) in &combinations {
+            for &hit in &cycle.cyclic_hits {
+                if let Some(congruence) = crt(congruence, (i128::from(hit), i128::from(cycle.period)))? {
+                    let floor = floor.max(hit);
+                    next.entry(congruence)
+                        .and_modify(|lowest: &mut u64| *lowest = (*lowest).min(floor))
+                        .or_insert(floor);
+                }
+            }
+        }
+        combinations = next;
+    }
+
+    let cyclic = combinations
+        .into_iter()
+        .map(|((n, modulus), floor)| {
+            let k = (i128::from(floor) - n + modulus - 1).div_euclid(modulus).max(0);
+            n + k * modulus
+        })
+        .min()
+        .map(u64::try_from)
+        .transpose()
+        .map_err(|_| OVERFLOW.to_string())?;
+
+    transient
+        .into_iter()
+        .chain(cyclic)
+        .min()
+        .ok_or_else(|| "walks never reach their ends at the same step".to_string())
+}
+
+/// Steps through the hits of the walk with the fewest of them, in increasing order, until every
+/// other walk hits too. Past the longest transient plus the lcm of the periods the walks repeat, so
+/// the search stops there.
+fn search(cycles: &[Cycle]) -> Result<u64, String> {
+    let Some(fewest) = cycles
+        .iter()
+        .min_by_key(|cycle| cycle.transient_hits.len() + cycle.cyclic_hits.len())
+    else {
+        return Ok(0);
+    };
+    let pre_period = cycles.iter().map(|cycle| cycle.pre_period).max().unwrap_or_default();
+    let lcm = cycles.iter().try_fold(1u64, |lcm, cycle| {
+        let (divisor, _, _) = extended_gcd(i128::from(lcm), i128::from(cycle.period));
+        lcm.checked_mul(cycle.period / u64::try_from(divisor).ok()?)
+    });
+    let bound = lcm.and_then(|lcm| lcm.checked_add(pre_period)).unwrap_or(u64::MAX);
+
+    let cyclic = (0..)
+        .map_while(|k: u64| k.checked_mul(fewest.period))
+        .take_while(|&offset| offset < bound)
+        .flat_map(|offset| fewest.cyclic_hits.iter().map_while(move |&hit| hit.checked_add(offset)));
+    fewest
+        .transient_hits
+        .iter()
+        .copied()
+        .chain(cyclic)
+        .take_while(|&step| step < bound)
+        .find(|&step| cycles.iter().all(|cycle| cycle.hits(step)))
+        .ok_or_else(|| "walks never reach their ends at the same step".to_string())
+}
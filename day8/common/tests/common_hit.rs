@@ -0,0 +1,63 @@
+use day8common::{first_common_hit, Cycle};
+
+fn cycle(pre_period: u64, period: u64, hit: impl Fn(u64) -> bool) -> Cycle {
+    let hits = (0..pre_period + period).filter(|&step| hit(step));
+    Cycle {
+        pre_period,
+        period,
+        transient_hits: hits.clone().filter(|&step| step < pre_period).collect(),
+        cyclic_hits: hits.filter(|&step| step >= pre_period).collect(),
+    }
+}
+
+fn brute_force(cycles: &[Cycle], bound: u64) -> Option<u64> {
+    (0..bound).find(|&step| cycles.iter().all(|cycle| cycle.hits(step)))
+}
+
+#[test]
+fn coprime_periods_hitting_everywhere() {
+    let cycles = [199, 200, 201, 203].map(|period| cycle(0, period, |_| true));
+    assert_eq!(first_common_hit(&cycles), Ok(0));
+
+    let cycles = [199, 200, 201, 203].map(|period| cycle(3, period, |step| step >= 5));
+    assert_eq!(first_common_hit(&cycles), Ok(5));
+}
+
+#[test]
+fn many_hits_agree_with_brute_force() {
+    let periods = [29, 31, 33, 35];
+    let bound = 4 + 29 * 31 * 33 * 35;
+    for seed in 0..20u64 {
+        let cycles = (0u64..)
+            .zip(periods)
+            .map(|(walk, period)| {
+                cycle(walk, period, move |step| {
+                    (step * (seed + 3) + walk * 5) % (period + 2) < period - 4
+                })
+            })
+            .collect::<Vec<_>>();
+        let expected = brute_force(&cycles, bound);
+        assert_eq!(first_common_hit(&cycles).ok(), expected, "seed {seed}");
+        assert_eq!(
+            first_common_hit(&cycles[..2]).ok(),
+            brute_force(&cycles[..2], bound),
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn large_coprime_periods_report_overflow() {
+    let periods = [
+        1_000_003, 1_000_033, 1_000_037, 1_000_039, 1_000_081, 1_000_099, 1_000_117,
+    ];
+    let cycles = periods.map(|period| cycle(0, period, |step| step == period - 1));
+    assert_eq!(
+        first_common_hit(&cycles),
+        Err("first common hit overflows u64".to_string())
+    );
+
+    // three of them still fit, and line up one step before the lcm
+    let lcm = periods[..3].iter().product::<u64>();
+    assert_eq!(first_common_hit(&cycles[..3]), Ok(lcm - 1));
+}
@@ -0,0 +1,50 @@
+use day8common::Network;
+
+#[test]
+fn rejects_nodes_defined_twice() {
+    let input = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nAAA = (AAA, BBB)";
+    let error = Network::parse(input.lines().map(str::to_string)).unwrap_err();
+    assert_eq!(error.to_string(), "line 5: node AAA is defined twice");
+}
+
+fn parse(nodes: &str) -> Result<Network, String> {
+    let input = format!("LR\n\n{nodes}");
+    Network::parse(input.lines().map(str::to_string)).map_err(|e| e.to_string())
+}
+
+#[test]
+fn accepts_long_names_and_extra_whitespace() {
+    let network = parse("START_1 = (node22, END)\n  node22   =(  END ,START_1 )  \n\nEND = (END, END)").unwrap();
+    assert_eq!(network.len(), 3);
+    let [start, middle, end] = ["START_1", "node22", "END"].map(|name| network.id(name).unwrap());
+    assert_eq!(network.next(start, 0), middle);
+    assert_eq!(network.next(middle, 0), end);
+    assert_eq!(network.next(middle, 1), start);
+}
+
+#[test]
+fn rejects_malformed_nodes() {
+    for node in [
+        "AAA = BBB, CCC",
+        "AAA = (BBB)",
+        "AAA = (BBB, CCC",
+        "AAA (BBB, CCC)",
+        " = (BBB, CCC)",
+        "AAA = (, CCC)",
+        "A-A = (BBB, CCC)",
+        "AAA = (B B, CCC)",
+    ] {
+        assert_eq!(
+            parse(&format!("ZZZ = (ZZZ, ZZZ)\n{node}")).unwrap_err(),
+            format!("line 4: expected `NAME = (LEFT, RIGHT)`, got {node}")
+        );
+    }
+}
+
+#[test]
+fn rejects_undefined_nodes() {
+    assert_eq!(
+        parse("AAA = (BBB, AAA)").unwrap_err(),
+        "node BBB is referenced but never defined"
+    );
+}
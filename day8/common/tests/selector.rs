@@ -0,0 +1,35 @@
+use day8common::Selector;
+
+fn names(names: &[&str]) -> Selector {
+    Selector::Names(names.iter().map(ToString::to_string).collect())
+}
+
+#[test]
+fn parses_each_kind() {
+    assert_eq!("suffix:Z".parse(), Ok(Selector::Suffix("Z".to_string())));
+    assert_eq!("prefix:AB".parse(), Ok(Selector::Prefix("AB".to_string())));
+    assert_eq!("names:AAA".parse(), Ok(names(&["AAA"])));
+    assert_eq!("names:AAA,11A,ZZZ".parse(), Ok(names(&["AAA", "11A", "ZZZ"])));
+}
+
+#[test]
+fn rejects_unknown_kinds() {
+    for selector in ["Z", "", "suffixZ", "infix:A", "Suffix:Z"] {
+        assert_eq!(
+            selector.parse::<Selector>(),
+            Err(format!(
+                "invalid selector {selector}, expected suffix:X, prefix:X or names:X,Y"
+            ))
+        );
+    }
+}
+
+#[test]
+fn matches_names() {
+    let suffix = "suffix:Z".parse::<Selector>().unwrap();
+    assert!(suffix.matches("11Z") && !suffix.matches("Z11"));
+    let prefix = "prefix:A".parse::<Selector>().unwrap();
+    assert!(prefix.matches("AZZ") && !prefix.matches("ZZA"));
+    let names = "names:AAA,BBB".parse::<Selector>().unwrap();
+    assert!(names.matches("BBB") && !names.matches("AAAA"));
+}
@@ -0,0 +1,63 @@
+use benchkit::{rate, XorShift};
+use day9common::extrapolate_in_place;
+use std::hint::black_box;
+use std::ops::Deref;
+use std::time::Instant;
+
+const SEQUENCES: u32 = 20_000;
+const LENGTH: i64 = 500;
+
+/// The allocating version, one `Vec` per level.
+fn resolve<T: Deref<Target = [i64]>>(numbers: T) -> Option<i64> {
+    Some(
+        *numbers.last()?
+            + match (0..numbers.len() - 1).fold(
+                (true, Vec::with_capacity(numbers.len() - 1)),
+                |(zeroes, mut acc), i| {
+                    let result = numbers[i + 1] - numbers[i];
+                    let zeroes = zeroes && result == 0;
+                    acc.push(result);
+                    (zeroes, acc)
+                },
+            ) {
+                (true, _) => 0,
+                (_, next) => resolve(next)?,
+            },
+    )
+}
+
+fn main() {
+    let mut rng = XorShift::new(0x2023_1209);
+    let sequences = (0..SEQUENCES)
+        .map(|_| {
+            let degree = rng.next_below(6);
+            let coefficients = (0..=degree)
+                .map(|_| rng.next_below(19).cast_signed() - 9)
+                .collect::<Vec<_>>();
+            (0..LENGTH)
+                .map(|x| coefficients.iter().rev().fold(0, |value, c| value * x + c))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let recursive = sequences
+        .iter()
+        .map(|sequence| resolve(black_box(sequence.clone())).unwrap_or_default())
+        .sum::<i64>();
+    println!("{:>16}: {}", "recursive", rate(SEQUENCES, "sequences", start));
+
+    let start = Instant::now();
+    let mut buffer = Vec::with_capacity(usize::try_from(LENGTH).unwrap_or_default());
+    let in_place = sequences
+        .iter()
+        .map(|sequence| {
+            buffer.clear();
+            buffer.extend_from_slice(black_box(sequence));
+            extrapolate_in_place(&mut buffer, LENGTH).unwrap_or_default()
+        })
+        .sum::<i64>();
+    println!("{:>16}: {}", "in place", rate(SEQUENCES, "sequences", start));
+
+    assert_eq!(recursive, in_place);
+}
@@ -0,0 +1,187 @@
+use num_bigint::BigInt;
+use std::env::args;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use tokenizer::numbers;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalysisError {
+    Empty,
+    /// The difference table ran out of elements before reaching a row of zeros.
+    NotPolynomial {
+        rows: usize,
+    },
+    Overflow,
+}
+
+impl Display for AnalysisError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisError::Empty => write!(f, "empty sequence"),
+            AnalysisError::NotPolynomial { rows } => write!(
+                f,
+                "not a polynomial: the difference table has no row of zeros after {rows} rows"
+            ),
+            AnalysisError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl Error for AnalysisError {}
+
+/// Newton forward-difference form of a sequence, evaluable at any integer index.
+#[derive(Debug, Clone)]
+pub struct Extrapolator {
+    /// First element of each non-zero row of the difference table, the sequence itself first.
+    diagonal: Vec<BigInt>,
+}
+
+impl Extrapolator {
+    /// Builds the difference table once, keeping only its leading diagonal.
+    ///
+    /// # Errors
+    ///
+    /// Fails on an empty sequence, and when no row of the table is all zeros, as the values then
+    /// do not pin down a polynomial.
+    pub fn new(values: &[i64]) -> Result<Self, AnalysisError> {
+        if values.is_empty() {
+            return Err(AnalysisError::Empty);
+        }
+        let mut row = values.iter().copied().map(BigInt::from).collect::<Vec<_>>();
+        let mut diagonal = Vec::with_capacity(values.len());
+        loop {
+            match row.first() {
+                None => return Err(AnalysisError::NotPolynomial { rows: diagonal.len() }),
+                Some(_) if row.iter().all(|n| *n == BigInt::ZERO) => break,
+                Some(first) => diagonal.push(first.clone()),
+            }
+            row = row.windows(2).map(|pair| &pair[1] - &pair[0]).collect();
+        }
+        Ok(Extrapolator { diagonal })
+    }
+
+    /// Degree of the polynomial, `None` for a sequence of zeros.
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        self.diagonal.len().checked_sub(1)
+    }
+
+    /// Value at `index`, where the input sequence sits at `0..len`. Negative indices extrapolate
+    /// backwards.
+    ///
+    /// Sums `C(index, k) * Δᵏ` over the diagonal, with the binomial generalised to negative
+    /// indices.
+    #[must_use]
+    pub fn at(&self, index: i64) -> BigInt {
+        let index = BigInt::from(index);
+        let mut binomial = BigInt::from(1);
+        let mut sum = BigInt::ZERO;
+        for (k, difference) in (0i64..).zip(&self.diagonal) {
+            sum += &binomial * difference;
+            // the product of k + 1 consecutive integers is divisible by (k + 1)!, so this is exact
+            binomial = binomial * (&index - k) / (k + 1);
+        }
+        sum
+    }
+}
+
+/// Reduces `buffer` in place to the leading diagonal of its difference table, returning the number
+/// of non-zero rows. Row `k` is built over row `k - 1` from the back, so `buffer[k]` is left holding
+/// its first element.
+///
+/// # Errors
+///
+/// As [`Extrapolator::new`], and on differences that overflow an `i64`.
+pub fn diagonal_in_place(buffer: &mut [i64]) -> Result<usize, AnalysisError> {
+    if buffer.is_empty() {
+        return Err(AnalysisError::Empty);
+    }
+    for rows in 0..buffer.len() {
+        if buffer[rows..].iter().all(|&n| n == 0) {
+            return Ok(rows);
+        }
+        for i in (rows + 1..buffer.len()).rev() {
+            buffer[i] = buffer[i].checked_sub(buffer[i - 1]).ok_or(AnalysisError::Overflow)?;
+        }
+    }
+    Err(AnalysisError::NotPolynomial { rows: buffer.len() })
+}
+
+/// [`Extrapolator::at`] without allocating, overwriting `buffer` with the difference table.
+///
+/// # Errors
+///
+/// As [`diagonal_in_place`], and when the value or an intermediate term overflows.
+pub fn extrapolate_in_place(buffer: &mut [i64], index: i64) -> Result<i64, AnalysisError> {
+    let rows = diagonal_in_place(buffer)?;
+    let index = i128::from(index);
+    let mut binomial = 1i128;
+    let mut sum = 0i128;
+    for (k, &difference) in (0i128..).zip(&buffer[..rows]) {
+        if k > 0 {
+            // updated before use so the unused binomial past the last row cannot overflow
+            binomial = binomial.checked_mul(index - k + 1).ok_or(AnalysisError::Overflow)? / k;
+        }
+        let term = binomial.checked_mul(i128::from(difference));
+        sum = term
+            .and_then(|term| sum.checked_add(term))
+            .ok_or(AnalysisError::Overflow)?;
+    }
+    i64::try_from(sum).map_err(|_| AnalysisError::Overflow)
+}
+
+/// How far to extrapolate, from `--steps N`.
+///
+/// # Errors
+///
+/// Fails when the value after `--steps` is not an integer.
+pub fn steps() -> Result<i64, Box<dyn Error>> {
+    let mut args = args().skip_while(|arg| arg != "--steps").skip(1);
+    Ok(args.next().map_or(Ok(1), |arg| arg.parse())?)
+}
+
+/// Sums each line's sequence evaluated at `index(len)`, trying [`extrapolate_in_place`] first and
+/// falling back to [`Extrapolator`] when that overflows. With `--degrees`, each line's degree is
+/// reported on stderr.
+///
+/// # Errors
+///
+/// Fails on read errors, invalid numbers and sequences that cannot be extrapolated, naming the
+/// line.
+pub fn sum_lines(mut input: impl BufRead, index: impl Fn(i64) -> i64) -> Result<BigInt, Box<dyn Error>> {
+    let degrees = args().any(|arg| arg == "--degrees");
+    let mut line = String::new();
+    let mut buffer = Vec::new();
+    let mut sum = BigInt::ZERO;
+    for number in 1.. {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        buffer.clear();
+        for token in numbers::<i64>(&line) {
+            buffer.push(token?.0);
+        }
+        let exact = || -> Result<Extrapolator, Box<dyn Error>> {
+            let numbers = numbers::<i64>(&line)
+                .map(|token| token.map(|(n, _)| n))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Extrapolator::new(&numbers).map_err(|err| format!("line {number}: {err}"))?)
+        };
+        if degrees {
+            match exact()?.degree() {
+                Some(degree) => eprintln!("line {number}: degree {degree}"),
+                None => eprintln!("line {number}: all zeros"),
+            }
+        }
+        let index = index(i64::try_from(buffer.len())?);
+        match extrapolate_in_place(&mut buffer, index) {
+            Ok(value) => sum += value,
+            // the buffer was overwritten, so parse again for the exact path
+            Err(AnalysisError::Overflow) => sum += exact()?.at(index),
+            Err(err) => Err(format!("line {number}: {err}"))?,
+        }
+    }
+    Ok(sum)
+}
@@ -0,0 +1,99 @@
+use benchkit::XorShift;
+use day9common::{diagonal_in_place, extrapolate_in_place, sum_lines, AnalysisError, Extrapolator};
+use num_bigint::BigInt;
+use std::io::Cursor;
+
+/// `coefficients[k] * x^k` summed, exactly.
+fn evaluate(coefficients: &[i64], x: i64) -> BigInt {
+    coefficients.iter().rev().fold(BigInt::ZERO, |value, &c| value * x + c)
+}
+
+fn sequence(coefficients: &[i64], length: i64) -> Vec<i64> {
+    (0..length)
+        .map(|x| i64::try_from(evaluate(coefficients, x)).unwrap())
+        .collect()
+}
+
+#[test]
+fn evaluates_far_from_the_input() {
+    let coefficients = [7, -5, 0, 2];
+    let extrapolator = Extrapolator::new(&sequence(&coefficients, 6)).unwrap();
+    for index in [-1000, -10, -1, 0, 5, 6, 1005] {
+        assert_eq!(extrapolator.at(index), evaluate(&coefficients, index), "index {index}");
+    }
+
+    let example = Extrapolator::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+    assert_eq!(example.at(-1), BigInt::from(5));
+    assert_eq!(example.at(6), BigInt::from(68));
+}
+
+#[test]
+fn sums_lines_a_thousand_steps_ahead() {
+    let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n";
+    let sum = sum_lines(Cursor::new(input), |len| len - 1 + 1000).unwrap();
+    // 3x and (x + 1)(x + 2) / 2 at x = 1005
+    assert_eq!(sum, BigInt::from(3 * 1005 + 1006 * 1007 / 2));
+
+    // past i64, so only the exact fallback can answer
+    let input = "0 4000000000000000000 8000000000000000000\n";
+    let sum = sum_lines(Cursor::new(input), |len| len - 1 + 1000).unwrap();
+    assert_eq!(sum, BigInt::from(4_000_000_000_000_000_000u64) * 1002);
+}
+
+#[test]
+fn reports_the_degree() {
+    let degree = |values: &[i64]| Extrapolator::new(values).unwrap().degree();
+    assert_eq!(degree(&[0, 0, 0]), None);
+    assert_eq!(degree(&[4, 4]), Some(0));
+    assert_eq!(degree(&[0, 3, 6, 9, 12, 15]), Some(1));
+    assert_eq!(degree(&[1, 3, 6, 10, 15, 21]), Some(2));
+    assert_eq!(degree(&[10, 13, 16, 21, 30, 45]), Some(3));
+}
+
+#[test]
+fn rejects_sequences_without_a_row_of_zeros() {
+    let cases: [(&[i64], AnalysisError); 4] = [
+        (&[], AnalysisError::Empty),
+        (&[5], AnalysisError::NotPolynomial { rows: 1 }),
+        (&[1, 2, 4, 8], AnalysisError::NotPolynomial { rows: 4 }),
+        (&[1, 0, 0, 0], AnalysisError::NotPolynomial { rows: 4 }),
+    ];
+    for (values, error) in cases {
+        assert_eq!(Extrapolator::new(values).unwrap_err(), error, "{values:?}");
+        assert_eq!(diagonal_in_place(&mut values.to_vec()), Err(error.clone()));
+        assert_eq!(extrapolate_in_place(&mut values.to_vec(), 0), Err(error));
+    }
+
+    let error = sum_lines(Cursor::new("1 2 3\n1 2 4 8\n"), |len| len).unwrap_err();
+    assert!(error.to_string().starts_with("line 2: not a polynomial"), "{error}");
+}
+
+#[test]
+fn in_place_agrees_with_the_exact_path() {
+    let mut rng = XorShift::new(0x2023_1209);
+    for _ in 0..2000 {
+        let degree = rng.next_below(8);
+        let coefficients = (0..=degree)
+            .map(|_| rng.next_below(2001).cast_signed() - 1000)
+            .collect::<Vec<_>>();
+        let length = degree.cast_signed() + 2 + rng.next_below(10).cast_signed();
+        let values = sequence(&coefficients, length);
+        let extrapolator = Extrapolator::new(&values).unwrap();
+
+        let rows = diagonal_in_place(&mut values.clone()).unwrap();
+        assert_eq!(rows, extrapolator.degree().map_or(0, |degree| degree + 1));
+
+        let index = rng.next_below(4001).cast_signed() - 2000;
+        let exact = extrapolator.at(index);
+        match extrapolate_in_place(&mut values.clone(), index) {
+            Ok(value) => assert_eq!(BigInt::from(value), exact, "{coefficients:?} at {index}"),
+            Err(error) => {
+                assert_eq!(error, AnalysisError::Overflow);
+                assert!(
+                    i64::try_from(&exact).is_err() || degree > 4,
+                    "{coefficients:?} at {index}"
+                );
+            }
+        }
+    }
+}
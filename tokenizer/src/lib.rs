@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenError {
+    pub token: String,
+    pub span: Range<usize>,
+    pub reason: String,
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid number `{}` at {}..{}: {}",
+            self.token, self.span.start, self.span.end, self.reason
+        )
+    }
+}
+
+impl Error for TokenError {}
+
+pub type Token<T> = Result<(T, Range<usize>), TokenError>;
+
+fn parse<T>(input: &str, span: Range<usize>, offset: usize) -> Token<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let token = &input[span.clone()];
+    let span = span.start + offset..span.end + offset;
+    match token.parse() {
+        Ok(value) => Ok((value, span)),
+        Err(e) => Err(TokenError {
+            token: token.to_string(),
+            span,
+            reason: e.to_string(),
+        }),
+    }
+}
+
+/// Whitespace separated list of numbers, where every token must parse as `T`.
+pub struct Numbers<'a, T> {
+    input: &'a str,
+    position: usize,
+    offset: usize,
+    value: PhantomData<T>,
+}
+
+#[must_use]
+pub fn numbers<T>(input: &str) -> Numbers<'_, T> {
+    Numbers {
+        input,
+        position: 0,
+        offset: 0,
+        value: PhantomData,
+    }
+}
+
+impl<T> Numbers<'_, T> {
+    /// Shifts reported spans, for when `input` was sliced out of a longer line.
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl<T> Iterator for Numbers<'_, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Item = Token<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+        let start = self.position + bytes[self.position..].iter().position(|c| !c.is_ascii_whitespace())?;
+        let end = bytes[start..]
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .map_or(bytes.len(), |len| start + len);
+        self.position = end;
+        Some(parse(self.input, start..end, self.offset))
+    }
+}
+
+/// Digit runs found anywhere in `input`, ignoring whatever surrounds them.
+pub struct EmbeddedNumbers<'a, T> {
+    input: &'a str,
+    position: usize,
+    signed: bool,
+    value: PhantomData<T>,
+}
+
+/// With `signed`, a `-` directly in front of digits belongs to the number unless it follows a digit.
+#[must_use]
+pub fn embedded_numbers<T>(input: &str, signed: bool) -> EmbeddedNumbers<'_, T> {
+    EmbeddedNumbers {
+        input,
+        position: 0,
+        signed,
+        value: PhantomData,
+    }
+}
+
+impl<T> Iterator for EmbeddedNumbers<'_, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Item = Token<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+        let digit = self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let start = if self.signed
+            && digit > 0
+            && bytes[digit - 1] == b'-'
+            && (digit < 2 || !bytes[digit - 2].is_ascii_digit())
+            && digit > self.position
+        {
+            digit - 1
+        } else {
+            digit
+        };
+        let end = bytes[digit..]
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .map_or(bytes.len(), |len| digit + len);
+        self.position = end;
+        Some(parse(self.input, start..end, 0))
+    }
+}
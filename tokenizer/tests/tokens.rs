@@ -0,0 +1,50 @@
+use std::ops::Range;
+use tokenizer::{embedded_numbers, numbers, Token, TokenError};
+
+fn values<T>(tokens: impl Iterator<Item = Token<T>>) -> Vec<(T, Range<usize>)> {
+    tokens.collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn numbers_skip_tabs_and_runs_of_spaces() {
+    assert_eq!(
+        values(numbers::<u32>("1\t 22   333\t")),
+        [(1, 0..1), (22, 3..5), (333, 8..11)]
+    );
+    assert!(values(numbers::<u32>(" \t  ")).is_empty());
+}
+
+#[test]
+fn offset_shifts_spans() {
+    assert_eq!(values(numbers::<i64>("7  -8").offset(10)), [(7, 10..11), (-8, 13..15)]);
+
+    let error = numbers::<u8>("x").offset(4).next().unwrap().unwrap_err();
+    assert_eq!(error.span, 4..5);
+}
+
+#[test]
+fn errors_point_at_the_bad_token() {
+    let mut tokens = numbers::<u8>("1  x2 300");
+    assert_eq!(tokens.next().unwrap(), Ok((1, 0..1)));
+
+    let TokenError { token, span, .. } = tokens.next().unwrap().unwrap_err();
+    assert_eq!((token.as_str(), span), ("x2", 3..5));
+
+    let TokenError { token, span, reason } = tokens.next().unwrap().unwrap_err();
+    assert_eq!((token.as_str(), span), ("300", 6..9));
+    assert!(reason.contains("too large"), "{reason}");
+    assert!(tokens.next().is_none());
+
+    let error = embedded_numbers::<u8>("ab256c", false).next().unwrap().unwrap_err();
+    assert_eq!(error.span, 2..5);
+}
+
+#[test]
+fn minus_signs_only_count_when_signed_and_not_after_a_digit() {
+    assert_eq!(values(embedded_numbers::<i64>("a-1", true)), [(-1, 1..3)]);
+    assert_eq!(values(embedded_numbers::<i64>("a-1", false)), [(1, 2..3)]);
+    assert_eq!(values(embedded_numbers::<i64>("1-2", true)), [(1, 0..1), (2, 2..3)]);
+    assert_eq!(values(embedded_numbers::<i64>("--3", true)), [(-3, 1..3)]);
+    assert_eq!(values(embedded_numbers::<i64>("-6", true)), [(-6, 0..2)]);
+    assert_eq!(values(embedded_numbers::<i64>("-4.-5", true)), [(-4, 0..2), (-5, 3..5)]);
+}